@@ -1,10 +1,8 @@
 use bevy::{
     asset::RenderAssetUsages,
     color::palettes::css::{WHITE, YELLOW},
-    mesh::Indices,
     platform::collections::HashMap,
     prelude::*,
-    render::render_resource::PrimitiveTopology,
     window::PrimaryWindow,
 };
 use hexx::{shapes, *};
@@ -124,16 +122,9 @@ fn higlight_hovered(
 
 /// Compute a bevy mesh from the layout
 fn hexagonal_column(hex_layout: &HexLayout) -> Mesh {
-    let mesh_info = ColumnMeshBuilder::new(hex_layout, COLUMN_HEIGHT)
+    ColumnMeshBuilder::new(hex_layout, COLUMN_HEIGHT)
         .without_bottom_face()
         .center_aligned()
-        .build();
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-    .with_inserted_indices(Indices::U16(mesh_info.indices))
+        .build()
+        .into_bevy_mesh(RenderAssetUsages::RENDER_WORLD)
 }
@@ -1,9 +1,7 @@
 use bevy::{
     asset::RenderAssetUsages,
     color::palettes::css::{BLUE, RED, WHITE},
-    mesh::Indices,
     prelude::*,
-    render::render_resource::PrimitiveTopology,
 };
 use hexx::{shapes, *};
 
@@ -60,17 +58,10 @@ fn setup_grid(
 
 /// Compute a bevy mesh from the layout
 fn hexagonal_plane(hex_layout: &HexLayout) -> Mesh {
-    let mesh_info = PlaneMeshBuilder::new(hex_layout)
+    PlaneMeshBuilder::new(hex_layout)
         .with_scale(Vec3::splat(0.9))
         .facing(Vec3::Z)
         .center_aligned()
-        .build();
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-    .with_inserted_indices(Indices::U16(mesh_info.indices))
+        .build()
+        .into_bevy_mesh(RenderAssetUsages::RENDER_WORLD)
 }
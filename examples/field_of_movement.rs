@@ -1,10 +1,8 @@
 use bevy::{
     asset::RenderAssetUsages,
     color::palettes::css::{GRAY, LIME, WHITE, YELLOW},
-    mesh::Indices,
     platform::collections::HashSet,
     prelude::*,
-    render::render_resource::PrimitiveTopology,
     window::PrimaryWindow,
 };
 use hexx::{
@@ -138,16 +136,9 @@ fn setup_grid(
 
 /// Compute a bevy mesh from the layout
 fn hexagonal_plane(hex_layout: &HexLayout) -> Mesh {
-    let mesh_info = PlaneMeshBuilder::new(hex_layout)
+    PlaneMeshBuilder::new(hex_layout)
         .facing(Vec3::Z)
         .center_aligned()
-        .build();
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-    .with_inserted_indices(Indices::U16(mesh_info.indices))
+        .build()
+        .into_bevy_mesh(RenderAssetUsages::RENDER_WORLD)
 }
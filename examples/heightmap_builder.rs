@@ -1,6 +1,6 @@
 use bevy::{
-    asset::RenderAssetUsages, ecs::system::RunSystemOnce, input::mouse::MouseMotion, mesh::Indices,
-    platform::collections::HashMap, prelude::*, render::render_resource::PrimitiveTopology,
+    ecs::system::RunSystemOnce, input::mouse::MouseMotion, platform::collections::HashMap,
+    prelude::*,
 };
 use bevy_egui::{
     EguiContext, EguiPlugin, EguiPrimaryContextPass,
@@ -26,6 +26,8 @@ struct BuilderParams {
     pub range: u32,
     pub scale: Vec3,
     pub fill_holes: bool,
+    pub smooth: bool,
+    pub subdivisions: u32,
     pub sides_options: Option<FaceOptions>,
     pub caps_options: Option<FaceOptions>,
 }
@@ -116,6 +118,12 @@ fn show_ui(world: &mut World) {
                 ui.label("Fill Holes");
                 ui.add(egui::Checkbox::without_text(&mut params.fill_holes));
                 ui.end_row();
+                ui.label("Smooth");
+                ui.add(egui::Checkbox::without_text(&mut params.smooth));
+                ui.end_row();
+                ui.label("Subdivisions");
+                ui.add(egui::DragValue::new(&mut params.subdivisions).range(0..=8));
+                ui.end_row();
             });
             ui.separator();
             egui::ScrollArea::vertical().show(ui, |ui| {
@@ -194,7 +202,7 @@ fn setup(
     commands.spawn((DirectionalLight::default(), transform));
     let layout = HexLayout::default();
     let mesh = HeightMapMeshBuilder::new(&layout, &HashMap::new()).build();
-    let mesh_handle = meshes.add(compute_mesh(mesh));
+    let mesh_handle = meshes.add(Mesh::from(mesh));
     let material_handle = materials.add(StandardMaterial {
         base_color_texture: Some(texture),
         cull_mode: None,
@@ -271,24 +279,16 @@ fn generate(params: Res<BuilderParams>, info: Res<HexInfo>, mut meshes: ResMut<A
     if params.fill_holes {
         new_mesh = new_mesh.with_default_height(0.0);
     }
-    let new_mesh = compute_mesh(new_mesh.build());
+    if params.smooth {
+        new_mesh =
+            new_mesh.with_smoothing(SmoothOptions::new().with_subdivisions(params.subdivisions));
+    }
+    let new_mesh = Mesh::from(new_mesh.build());
     // println!("Mesh has {} vertices", new_mesh.count_vertices());
     let mut mesh = meshes.get_mut(&info.mesh_handle).unwrap();
     *mesh = new_mesh;
 }
 
-/// Compute a bevy mesh from the layout
-fn compute_mesh(mesh_info: MeshInfo) -> Mesh {
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-    .with_inserted_indices(Indices::U16(mesh_info.indices))
-}
-
 impl Default for BuilderParams {
     fn default() -> Self {
         Self {
@@ -298,6 +298,8 @@ impl Default for BuilderParams {
             caps_options: Some(FaceOptions::new()),
             scale: Vec3::ONE,
             fill_holes: false,
+            smooth: false,
+            subdivisions: 2,
         }
     }
 }
@@ -3,9 +3,7 @@ use std::ops::Range;
 use bevy::{
     asset::RenderAssetUsages,
     color::palettes::css::{BLUE, RED, WHITE},
-    mesh::Indices,
     prelude::*,
-    render::render_resource::PrimitiveTopology,
 };
 use bevy_egui::EguiPlugin;
 use bevy_inspector_egui::{InspectorOptions, quick::ResourceInspectorPlugin};
@@ -111,6 +109,8 @@ fn setup_grid(
             mesh.merge_with(info);
             mesh
         });
+        // We remove the hidden faces between columns and weld the vertices
+        let mesh = mesh.optimized();
         let mesh = meshes.add(hex_mesh(mesh));
         commands.spawn((
             Name::new(format!("Chunk {} {}", chunk.x, chunk.y)),
@@ -125,14 +125,7 @@ fn setup_grid(
 
 /// Compute a bevy mesh from a hexx mesh
 fn hex_mesh(mesh_info: MeshInfo) -> Mesh {
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-    .with_inserted_indices(Indices::U16(mesh_info.indices))
+    mesh_info.into_bevy_mesh(RenderAssetUsages::RENDER_WORLD)
 }
 
 impl Default for MapSettings {
@@ -1,10 +1,7 @@
 use bevy::{
-    asset::RenderAssetUsages,
     input::mouse::MouseMotion,
-    mesh::Indices,
     pbr::wireframe::{Wireframe, WireframePlugin},
     prelude::*,
-    render::render_resource::PrimitiveTopology,
 };
 use bevy_egui::{
     EguiContext, EguiPlugin, EguiPrimaryContextPass,
@@ -265,7 +262,7 @@ fn setup(
         .with_subdivisions(params.subdivisions)
         .with_offset(Vec3::NEG_Y * params.height / 2.0)
         .build();
-    let mesh_handle = meshes.add(compute_mesh(mesh));
+    let mesh_handle = meshes.add(Mesh::from(mesh));
     let material_handle = materials.add(StandardMaterial {
         base_color_texture: Some(texture),
         cull_mode: None,
@@ -345,24 +342,12 @@ fn update_mesh(params: Res<BuilderParams>, info: Res<HexInfo>, mut meshes: ResMu
     if let Some(opts) = params.caps_inset {
         new_mesh = new_mesh.with_caps_inset_options(opts);
     }
-    let new_mesh = compute_mesh(new_mesh.build());
+    let new_mesh = Mesh::from(new_mesh.build());
     // println!("Mesh has {} vertices", new_mesh.count_vertices());
     let mut mesh = meshes.get_mut(&info.mesh_handle).unwrap();
     *mesh = new_mesh;
 }
 
-/// Compute a bevy mesh from the layout
-fn compute_mesh(mesh_info: MeshInfo) -> Mesh {
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-    .with_inserted_indices(Indices::U16(mesh_info.indices))
-}
-
 impl Default for BuilderParams {
     fn default() -> Self {
         Self {
@@ -0,0 +1,285 @@
+use super::pathfinding::{Node, reconstruct_path};
+use crate::{Hex, HexTopology, Unbounded};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+use std::collections::BinaryHeap;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+
+/// Distance map computed by [`dijkstra_map`].
+///
+/// Stores, for every reached coordinate, the accumulated cost from the closest
+/// source and the predecessor coordinate on the cheapest path, allowing to
+/// reconstruct paths to any reached coordinate without running a new search.
+#[derive(Debug, Clone, Default)]
+pub struct DijkstraMap {
+    costs: HashMap<Hex, u32>,
+    came_from: HashMap<Hex, Hex>,
+}
+
+impl DijkstraMap {
+    /// Returns the accumulated cost to reach `coord` from the closest source,
+    /// or `None` if `coord` was not reached
+    #[inline]
+    #[must_use]
+    pub fn cost(&self, coord: Hex) -> Option<u32> {
+        self.costs.get(&coord).copied()
+    }
+
+    /// Returns the predecessor of `coord` on the cheapest path from the closest
+    /// source.
+    ///
+    /// Returns `None` if `coord` was not reached or is a source
+    #[inline]
+    #[must_use]
+    pub fn predecessor(&self, coord: Hex) -> Option<Hex> {
+        self.came_from.get(&coord).copied()
+    }
+
+    /// Returns `true` if `coord` was reached
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, coord: Hex) -> bool {
+        self.costs.contains_key(&coord)
+    }
+
+    /// Returns the closest source to `coord`, or `None` if `coord` was not
+    /// reached
+    #[must_use]
+    pub fn source_of(&self, coord: Hex) -> Option<Hex> {
+        if !self.contains(coord) {
+            return None;
+        }
+        std::iter::successors(Some(coord), |c| self.predecessor(*c)).last()
+    }
+
+    /// Computes the cheapest path from the closest source to `target`,
+    /// including both the source and `target`.
+    ///
+    /// Returns `None` if `target` was not reached
+    #[must_use]
+    pub fn path_to(&self, target: Hex) -> Option<Vec<Hex>> {
+        self.contains(target)
+            .then(|| reconstruct_path(&self.came_from, target))
+    }
+
+    /// Number of reached coordinates, sources included
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    /// Returns `true` if no coordinate was reached
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    /// An iterator visiting all reached coordinates and their cost in
+    /// arbitrary order.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (Hex, u32)> + '_ {
+        self.costs.iter().map(|(k, v)| (*k, *v))
+    }
+
+    /// Returns the inner cost map
+    #[inline]
+    #[must_use]
+    pub const fn costs(&self) -> &HashMap<Hex, u32> {
+        &self.costs
+    }
+
+    /// Returns the inner predecessor map
+    #[inline]
+    #[must_use]
+    pub const fn predecessors(&self) -> &HashMap<Hex, Hex> {
+        &self.came_from
+    }
+
+    /// Consumes the map, returning the inner cost map
+    #[inline]
+    #[must_use]
+    pub fn into_costs(self) -> HashMap<Hex, u32> {
+        self.costs
+    }
+}
+
+/// Computes a [`DijkstraMap`] (uniform cost search) from one or many
+/// `sources`, up to a maximum accumulated cost of `budget`.
+///
+/// > See [`dijkstra_map_in`] for wrapping maps
+///
+/// Every coordinate reachable within `budget` is stored with its cost from the
+/// closest source and its predecessor, so paths to many targets can be
+/// retrieved from a single search (flee maps, influence maps, etc).
+///
+/// The `cost` parameter follows the same convention as
+/// [`a_star`](super::a_star): it should give the cost to go from a coordinate
+/// to its neighbor (`Some`) or indicate the neighbor is not included in the
+/// search (`None`).
+/// Sources always have a cost of zero.
+///
+/// # Arguments
+///
+/// * `sources` - start nodes
+/// * `budget` - maximum accumulated cost, coordinates exceeding it are ignored
+/// * `cost` - cost function taking a node pair (`a` -> `b`) and returning the
+///   logical cost to go from `a` to `b`
+///
+/// # Examples
+///
+/// - Compute distances from two sources with some forbidden tiles
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashSet;
+/// use hexx::algorithms::dijkstra_map;
+///
+/// let sources = [hex(0, 0), hex(10, 0)];
+/// let forbidden_coords: HashSet<Hex> = HashSet::new();
+/// // Add forbidden coordinates
+/// // forbidden_coords.insert(hex(2, 0));
+/// // ..
+/// let map = dijkstra_map(sources, 20, |_, b| {
+///     (!forbidden_coords.contains(&b)).then_some(1)
+/// });
+/// assert_eq!(map.cost(hex(5, 0)), Some(5));
+/// assert_eq!(map.cost(hex(7, 0)), Some(3));
+/// assert_eq!(map.source_of(hex(7, 0)), Some(hex(10, 0)));
+/// let path = map.path_to(hex(7, 0)).unwrap();
+/// assert_eq!(path.first(), Some(&hex(10, 0)));
+/// assert_eq!(path.last(), Some(&hex(7, 0)));
+/// ```
+pub fn dijkstra_map(
+    sources: impl IntoIterator<Item = Hex>,
+    budget: u32,
+    cost: impl Fn(Hex, Hex) -> Option<u32>,
+) -> DijkstraMap {
+    dijkstra_map_in(Unbounded, sources, budget, cost)
+}
+
+/// Computes a [`DijkstraMap`] from one or many `sources` in the given
+/// `topology`, up to a maximum accumulated cost of `budget`.
+///
+/// Neighbors are resolved through [`HexTopology::neighbors`], so the search
+/// crosses the seams of wrapping maps and only canonical coordinates are
+/// stored. Sources are canonicalized, and ignored if outside of the world.
+///
+/// > See [`dijkstra_map`] for the arguments
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::dijkstra_map_in;
+///
+/// let bounds = HexBounds::new(Hex::ZERO, 5);
+/// let map = dijkstra_map_in(bounds, [hex(5, 0)], 10, |_, _| Some(1));
+/// assert_eq!(map.len(), bounds.hex_count());
+/// // The opposite side is reached through the seam
+/// assert_eq!(map.cost(hex(-5, 5)), Some(1));
+/// ```
+pub fn dijkstra_map_in(
+    topology: impl HexTopology,
+    sources: impl IntoIterator<Item = Hex>,
+    budget: u32,
+    cost: impl Fn(Hex, Hex) -> Option<u32>,
+) -> DijkstraMap {
+    let mut open = BinaryHeap::new();
+    let mut map = DijkstraMap::default();
+    for source in sources.into_iter().filter_map(|s| topology.canonical(s)) {
+        map.costs.insert(source, 0);
+        open.push(Node {
+            coord: source,
+            score: 0,
+        });
+    }
+
+    while let Some(node) = open.pop() {
+        // Skip outdated entries
+        if map.costs[&node.coord] < node.score {
+            continue;
+        }
+        for neighbor in topology.neighbors(node.coord).into_iter().flatten() {
+            let Some(cost) = cost(node.coord, neighbor) else {
+                continue;
+            };
+            let neighbor_cost = node.score.saturating_add(cost);
+            if neighbor_cost > budget {
+                continue;
+            }
+            if map.costs.get(&neighbor).is_none_or(|c| *c > neighbor_cost) {
+                map.came_from.insert(neighbor, node.coord);
+                map.costs.insert(neighbor, neighbor_cost);
+                open.push(Node {
+                    coord: neighbor,
+                    score: neighbor_cost,
+                });
+            }
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::HexBounds;
+
+    #[test]
+    fn single_source_matches_distance() {
+        let map = dijkstra_map([Hex::ZERO], 10, |_, _| Some(1));
+        assert_eq!(map.len(), Hex::range_count(10) as usize);
+        for (coord, cost) in map.iter() {
+            assert_eq!(cost, coord.ulength());
+            let path = map.path_to(coord).unwrap();
+            assert_eq!(path.len(), cost as usize + 1);
+            assert_eq!(path[0], Hex::ZERO);
+        }
+        assert!(map.path_to(Hex::new(11, 0)).is_none());
+    }
+
+    #[test]
+    fn multiple_sources() {
+        let sources = [Hex::new(-5, 0), Hex::new(5, 0)];
+        let map = dijkstra_map(sources, 20, |_, b| (b.ulength() <= 10).then_some(1));
+        for (coord, cost) in map.iter() {
+            let expected = sources
+                .iter()
+                .map(|s| s.unsigned_distance_to(coord))
+                .min()
+                .unwrap();
+            assert_eq!(cost, expected);
+            let source = map.source_of(coord).unwrap();
+            assert_eq!(source.unsigned_distance_to(coord), expected);
+        }
+    }
+
+    #[test]
+    fn walls() {
+        // Wall on the `x = 1` column, except for the `y = 5` coordinate
+        let map = dijkstra_map([Hex::ZERO], 30, |_, b| (b.x != 1 || b.y == 5).then_some(1));
+        assert_eq!(map.cost(Hex::new(1, 0)), None);
+        let cost = map.cost(Hex::new(2, 0)).unwrap();
+        assert!(cost > 2);
+        let path = map.path_to(Hex::new(2, 0)).unwrap();
+        assert!(path.contains(&Hex::new(1, 5)));
+    }
+
+    #[test]
+    fn wrapping_bounds() {
+        let bounds = HexBounds::new(Hex::new(2, -1), 6);
+        let source = Hex::new(5, 2);
+        let map = dijkstra_map_in(bounds, [source], u32::MAX, |_, _| Some(1));
+        assert_eq!(map.len(), bounds.hex_count());
+        for (coord, cost) in map.iter() {
+            assert!(bounds.is_in_bounds(coord));
+            assert_eq!(cost, bounds.distance(source, coord));
+            let path = map.path_to(coord).unwrap();
+            assert!(path.windows(2).all(|w| bounds.distance(w[0], w[1]) == 1));
+        }
+    }
+}
@@ -1,8 +1,224 @@
-use crate::Hex;
+use super::{DijkstraMap, dijkstra_map_in};
+use crate::{Hex, HexTopology, Unbounded};
 #[cfg(feature = "bevy_platform")]
-use bevy_platform::collections::{HashMap, HashSet};
+use bevy_platform::collections::HashSet;
 #[cfg(not(feature = "bevy_platform"))]
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+
+/// Field of movement computed by [`movement_field`] or
+/// [`edge_movement_field`].
+///
+/// Stores every reachable coordinate with its accumulated movement cost and
+/// its parent coordinate, allowing to display move costs or remaining budgets
+/// and to reconstruct the path to any reachable coordinate.
+#[derive(Debug, Clone)]
+pub struct MovementField {
+    start: Hex,
+    budget: u32,
+    map: DijkstraMap,
+}
+
+impl MovementField {
+    /// Returns the start coordinate of the field
+    #[inline]
+    #[must_use]
+    pub const fn start(&self) -> Hex {
+        self.start
+    }
+
+    /// Returns the movement budget of the field
+    #[inline]
+    #[must_use]
+    pub const fn budget(&self) -> u32 {
+        self.budget
+    }
+
+    /// Returns `true` if `coord` is reachable
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, coord: Hex) -> bool {
+        self.map.contains(coord)
+    }
+
+    /// Returns the accumulated movement cost to reach `coord`, or `None` if
+    /// `coord` is not reachable
+    #[inline]
+    #[must_use]
+    pub fn cost(&self, coord: Hex) -> Option<u32> {
+        self.map.cost(coord)
+    }
+
+    /// Returns the movement budget left after reaching `coord`, or `None` if
+    /// `coord` is not reachable
+    #[inline]
+    #[must_use]
+    pub fn remaining_budget(&self, coord: Hex) -> Option<u32> {
+        self.cost(coord).map(|c| self.budget - c)
+    }
+
+    /// Returns the parent coordinate of `coord` on the cheapest path from the
+    /// start, or `None` if `coord` is not reachable or is the start
+    #[inline]
+    #[must_use]
+    pub fn parent(&self, coord: Hex) -> Option<Hex> {
+        self.map.predecessor(coord)
+    }
+
+    /// Computes the cheapest path from the start to `coord`, both included.
+    ///
+    /// Returns `None` if `coord` is not reachable
+    #[inline]
+    #[must_use]
+    pub fn path_to(&self, coord: Hex) -> Option<Vec<Hex>> {
+        self.map.path_to(coord)
+    }
+
+    /// An iterator visiting all reachable coordinates and their remaining
+    /// budget in arbitrary order.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (Hex, u32)> + '_ {
+        self.map.iter().map(|(h, c)| (h, self.budget - c))
+    }
+
+    /// An iterator visiting all reachable coordinates in arbitrary order.
+    #[must_use]
+    pub fn reachable(&self) -> impl ExactSizeIterator<Item = Hex> + '_ {
+        self.map.costs().keys().copied()
+    }
+
+    /// Number of reachable coordinates, start included
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if no coordinate is reachable
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the underlying [`DijkstraMap`]
+    #[inline]
+    #[must_use]
+    pub const fn dijkstra_map(&self) -> &DijkstraMap {
+        &self.map
+    }
+}
+
+/// Computes a [`MovementField`] around `coord` given a `budget`
+///
+/// This algorithm takes a `cost` function, which calculates and
+/// returns the cost of movement through a given `Hex` tile.
+/// The `cost` function should return an `Option<u32>`.
+/// A tile that returns a computable cost would return `Some(cost)`, whereas
+/// `None` should be returned for tiles that have no computable cost (i.e.
+/// cannot be moved through).
+///
+/// As for [`field_of_movement`], the algorithm will always add `+ 1` to the
+/// computed cost in order to avoid the possibility of unlimited movement range.
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::movement_field;
+///
+/// // Tiles on the `x = 1` column cost 2 more to go through
+/// let field = movement_field(Hex::ZERO, 5, |h| Some(if h.x == 1 { 2 } else { 0 }));
+/// assert_eq!(field.cost(hex(1, 0)), Some(3));
+/// assert_eq!(field.remaining_budget(hex(1, 0)), Some(2));
+/// assert_eq!(field.parent(hex(1, 0)), Some(Hex::ZERO));
+/// assert_eq!(field.path_to(hex(2, 0)).unwrap().len(), 3);
+/// assert!(!field.contains(hex(5, 0)));
+/// ```
+pub fn movement_field(coord: Hex, budget: u32, cost: impl Fn(Hex) -> Option<u32>) -> MovementField {
+    movement_field_in(Unbounded, coord, budget, cost)
+}
+
+/// Computes a [`MovementField`] around `coord` given a `budget` in the given
+/// `topology`, allowing movement across the seams of wrapping maps.
+///
+/// > See [`movement_field`] and [`dijkstra_map_in`](super::dijkstra_map_in)
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::movement_field_in;
+///
+/// let bounds = HexBounds::new(Hex::ZERO, 5);
+/// let field = movement_field_in(bounds, hex(5, 0), 1, |_| Some(0));
+/// assert!(field.contains(hex(-5, 5)));
+/// ```
+pub fn movement_field_in(
+    topology: impl HexTopology,
+    coord: Hex,
+    budget: u32,
+    cost: impl Fn(Hex) -> Option<u32>,
+) -> MovementField {
+    edge_movement_field_in(topology, coord, budget, |_, b| {
+        cost(b).map(|c| c.saturating_add(1))
+    })
+}
+
+/// Computes a [`MovementField`] around `coord` given a `budget` and a movement
+/// cost between neighbors.
+///
+/// The `cost` parameter follows the same convention as
+/// [`a_star`](super::a_star): it takes a node pair (`a` -> `b`) and returns the
+/// logical cost to go from `a` to `b` (`Some`) or indicates that `b` cannot be
+/// reached from `a` (`None`).
+///
+/// # Warning
+///
+/// Unlike [`movement_field`], no minimum cost is added. Zero costs on
+/// unbounded maps lead to an unlimited movement range and the algorithm will
+/// never end.
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::edge_movement_field;
+///
+/// // Climbing (moving away from the origin) costs 2, descending costs 1
+/// let field = edge_movement_field(Hex::ZERO, 4, |a, b| {
+///     Some(if b.ulength() > a.ulength() { 2 } else { 1 })
+/// });
+/// assert_eq!(field.cost(hex(2, 0)), Some(4));
+/// assert!(!field.contains(hex(3, 0)));
+/// ```
+pub fn edge_movement_field(
+    coord: Hex,
+    budget: u32,
+    cost: impl Fn(Hex, Hex) -> Option<u32>,
+) -> MovementField {
+    edge_movement_field_in(Unbounded, coord, budget, cost)
+}
+
+/// Computes a [`MovementField`] around `coord` given a `budget` and a movement
+/// cost between neighbors in the given `topology`.
+///
+/// `coord` should be a canonical coordinate
+/// (See [`HexTopology::canonical`]).
+///
+/// > See [`edge_movement_field`] and
+/// > [`dijkstra_map_in`](super::dijkstra_map_in)
+pub fn edge_movement_field_in(
+    topology: impl HexTopology,
+    coord: Hex,
+    budget: u32,
+    cost: impl Fn(Hex, Hex) -> Option<u32>,
+) -> MovementField {
+    MovementField {
+        start: coord,
+        budget,
+        map: dijkstra_map_in(topology, [coord], budget, cost),
+    }
+}
 
 /// Computes a field of movement around `coord` given a `budget`
 ///
@@ -17,10 +233,7 @@ use std::collections::{HashMap, HashSet};
 /// in order to avoid the possibility of unlimited movement range (i.e. a `Hex`
 /// instance will always have a minimum movement `cost` of 1).
 ///
-/// # Warning
-///
-/// The implementation of this function is pretty naive and has a high
-/// complexity. It is not suitable for production use
+/// > See [`movement_field`] to retrieve the movement costs and paths
 ///
 /// # Examples
 ///
@@ -65,37 +278,88 @@ pub fn field_of_movement(
     budget: u32,
     cost: impl Fn(Hex) -> Option<u32>,
 ) -> HashSet<Hex> {
-    let mut computed_costs = HashMap::with_capacity(Hex::range_count(budget) as usize);
-    computed_costs.insert(coord, 0);
-
-    // We cache the rings and costs
-    let rings: Vec<(Hex, u32)> = coord
-        .rings(1..=budget)
-        .flatten()
-        .filter_map(|h| cost(h).map(|c| (h, c)))
-        .collect();
-
-    let mut loop_again = true;
-    while loop_again {
-        loop_again = false;
-        for (coord, coord_cost) in &rings {
-            let Some(neighbor_cost) = coord
-                .all_neighbors()
-                .into_iter()
-                .filter_map(|n| computed_costs.get(&n))
-                .min()
-            else {
-                continue;
-            };
-            let computed_cost = coord_cost + 1 + neighbor_cost;
-            let res = computed_costs.insert(*coord, computed_cost);
-            if !loop_again && (res.is_none() || res != Some(computed_cost)) {
-                loop_again = true;
-            }
+    let mut res = HashSet::default();
+    field_of_movement_into(coord, budget, cost, &mut res);
+    res
+}
+
+/// Computes a field of movement around `coord` given a `budget` in the given
+/// `topology`, allowing movement across the seams of wrapping maps.
+///
+/// `coord` should be a canonical coordinate
+/// (See [`HexTopology::canonical`]).
+///
+/// > See [`field_of_movement`] and [`movement_field_in`]
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::field_of_movement_in;
+///
+/// let bounds = HexBounds::new(Hex::ZERO, 5);
+/// let reachable = field_of_movement_in(bounds, hex(5, 0), 1, |_| Some(0));
+/// assert!(reachable.contains(&hex(-5, 5)));
+/// assert!(reachable.iter().all(|h| bounds.is_in_bounds(*h)));
+/// ```
+pub fn field_of_movement_in(
+    topology: impl HexTopology,
+    coord: Hex,
+    budget: u32,
+    cost: impl Fn(Hex) -> Option<u32>,
+) -> HashSet<Hex> {
+    movement_field_in(topology, coord, budget, cost)
+        .reachable()
+        .collect()
+}
+
+/// Computes a field of movement around `coord` given a `budget`, writing the
+/// reachable coordinates into `output`
+///
+/// > See [`field_of_movement`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::HexBitSet};
+/// use hexx::algorithms::field_of_movement_into;
+///
+/// let mut reachable = HexBitSet::hexagonal(HexBounds::new(Hex::ZERO, 10));
+/// field_of_movement_into(Hex::ZERO, 3, |h| (h.x != 1).then_some(0), &mut reachable);
+/// assert!(reachable.contains(hex(-3, 0)));
+/// assert!(!reachable.contains(hex(1, 0)));
+/// ```
+pub fn field_of_movement_into(
+    coord: Hex,
+    budget: u32,
+    cost: impl Fn(Hex) -> Option<u32>,
+    output: &mut impl Extend<Hex>,
+) {
+    output.extend(movement_field(coord, budget, cost).reachable());
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_match_costs() {
+        let tile_cost = |h: Hex| match h.x.rem_euclid(3) {
+            0 => Some(0),
+            1 => Some(2),
+            _ => (h.y != 0).then_some(1),
+        };
+        let field = movement_field(Hex::ZERO, 8, tile_cost);
+        assert_eq!(field.remaining_budget(Hex::ZERO), Some(8));
+        for (coord, remaining) in field.iter() {
+            let path = field.path_to(coord).unwrap();
+            let cost: u32 = path[1..].iter().map(|h| tile_cost(*h).unwrap() + 1).sum();
+            assert_eq!(cost + remaining, 8);
+            assert!(coord.ulength() <= cost);
         }
+        let reachable = field_of_movement(Hex::ZERO, 8, tile_cost);
+        assert_eq!(reachable.len(), field.len());
+        assert!(reachable.iter().all(|h| field.contains(*h)));
     }
-    computed_costs
-        .into_iter()
-        .filter_map(|(coord, cost)| (cost <= budget).then_some(coord))
-        .collect()
 }
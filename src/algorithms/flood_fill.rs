@@ -0,0 +1,439 @@
+#[cfg(feature = "grid")]
+use crate::GridEdge;
+use crate::{EdgeDirection, Hex, storage::HexStore};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+/// Connected components computed by [`connected_components`].
+///
+/// Every component is a group of coordinates connected through their edges,
+/// identified by a *label* which is its index in [`Self::groups`].
+#[derive(Debug, Clone, Default)]
+pub struct Components {
+    groups: Vec<Vec<Hex>>,
+    labels: HashMap<Hex, usize>,
+}
+
+impl Components {
+    /// Returns the label of the component containing `coord`, or `None` if
+    /// `coord` is not part of any component
+    #[inline]
+    #[must_use]
+    pub fn label(&self, coord: Hex) -> Option<usize> {
+        self.labels.get(&coord).copied()
+    }
+
+    /// Returns the coordinates of the component with the given `label`
+    #[inline]
+    #[must_use]
+    pub fn group(&self, label: usize) -> Option<&[Hex]> {
+        self.groups.get(label).map(Vec::as_slice)
+    }
+
+    /// Returns the coordinates of the component containing `coord`
+    #[inline]
+    #[must_use]
+    pub fn component_of(&self, coord: Hex) -> Option<&[Hex]> {
+        self.group(self.label(coord)?)
+    }
+
+    /// Returns `true` if `a` and `b` belong to the same component
+    #[inline]
+    #[must_use]
+    pub fn are_connected(&self, a: Hex, b: Hex) -> bool {
+        self.label(a).is_some_and(|l| self.label(b) == Some(l))
+    }
+
+    /// Number of components
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns `true` if there are no components
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// An iterator visiting all components in label order
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[Hex]> + '_ {
+        self.groups.iter().map(Vec::as_slice)
+    }
+
+    /// Returns the components, indexed by label
+    #[inline]
+    #[must_use]
+    pub fn groups(&self) -> &[Vec<Hex>] {
+        &self.groups
+    }
+
+    /// Returns the inner label map
+    #[inline]
+    #[must_use]
+    pub const fn labels(&self) -> &HashMap<Hex, usize> {
+        &self.labels
+    }
+
+    /// Consumes the components, returning the groups indexed by label
+    #[inline]
+    #[must_use]
+    pub fn into_groups(self) -> Vec<Vec<Hex>> {
+        self.groups
+    }
+
+    /// Fills the component starting at `start` with the given `label`
+    fn fill(
+        &mut self,
+        start: Hex,
+        label: usize,
+        predicate: impl Fn(Hex) -> bool,
+        neighbor: impl Fn(Hex, EdgeDirection) -> Option<Hex>,
+    ) -> Vec<Hex> {
+        let mut group = vec![start];
+        let mut queue = VecDeque::from([start]);
+        self.labels.insert(start, label);
+        while let Some(coord) = queue.pop_front() {
+            for dir in EdgeDirection::ALL_DIRECTIONS {
+                let Some(next) = neighbor(coord, dir) else {
+                    continue;
+                };
+                if self.labels.contains_key(&next) || !predicate(next) {
+                    continue;
+                }
+                self.labels.insert(next, label);
+                group.push(next);
+                queue.push_back(next);
+            }
+        }
+        group
+    }
+
+    fn compute(
+        coords: impl IntoIterator<Item = Hex>,
+        predicate: impl Fn(Hex) -> bool,
+        neighbor: impl Fn(Hex, EdgeDirection) -> Option<Hex>,
+    ) -> Self {
+        let mut res = Self::default();
+        for coord in coords {
+            if res.labels.contains_key(&coord) || !predicate(coord) {
+                continue;
+            }
+            let group = res.fill(coord, res.groups.len(), &predicate, &neighbor);
+            res.groups.push(group);
+        }
+        res
+    }
+}
+
+/// Returns the neighbor of `coord` in `dir` unless their shared edge is a wall
+#[cfg(feature = "grid")]
+fn unwalled(is_wall: impl Fn(GridEdge) -> bool) -> impl Fn(Hex, EdgeDirection) -> Option<Hex> {
+    move |coord, direction| {
+        let edge = GridEdge {
+            origin: coord,
+            direction,
+        };
+        (!is_wall(edge) && !is_wall(edge.flipped())).then(|| coord + direction)
+    }
+}
+
+/// Computes the coordinates connected to `start` for which `predicate`
+/// returns `true`, using a breadth first *flood fill*.
+///
+/// The coordinates are returned in visit order, starting with `start`. If
+/// `start` doesn't match `predicate` the result is empty.
+///
+/// # Warning
+///
+/// The grid is infinite, so `predicate` must bound the filled area or the
+/// algorithm will never end.
+///
+/// > See [`flood_fill_with_walls`] to prevent crossing some edges
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::flood_fill;
+///
+/// // Fill a lake of radius 3, split in two by a bridge on the `x = 0` column
+/// let lake = flood_fill(hex(-1, 0), |h| h.ulength() <= 3 && h.x != 0);
+/// assert_eq!(lake.len(), (Hex::range_count(3) as usize - 7) / 2);
+/// assert!(lake.iter().all(|h| h.x < 0));
+/// ```
+pub fn flood_fill(start: Hex, predicate: impl Fn(Hex) -> bool) -> Vec<Hex> {
+    Components::compute([start], predicate, |h, dir| Some(h + dir))
+        .into_groups()
+        .pop()
+        .unwrap_or_default()
+}
+
+/// Computes the coordinates connected to `start` for which `predicate`
+/// returns `true`, without crossing the edges for which `is_wall` returns
+/// `true`.
+///
+/// Both orientations of an edge are checked, so `is_wall` may only recognize
+/// one of them (See [`GridEdge::equivalent`]).
+///
+/// > See [`flood_fill`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashSet;
+/// use hexx::algorithms::flood_fill_with_walls;
+///
+/// // Wall all around the origin
+/// let walls: HashSet<GridEdge> = Hex::ZERO.all_edges().into();
+/// let room = flood_fill_with_walls(hex(1, 0), |h| h.ulength() <= 2, |e| walls.contains(&e));
+/// assert_eq!(room.len(), Hex::range_count(2) as usize - 1);
+/// let room = flood_fill_with_walls(Hex::ZERO, |h| h.ulength() <= 2, |e| walls.contains(&e));
+/// assert_eq!(room, vec![Hex::ZERO]);
+/// ```
+#[cfg(feature = "grid")]
+pub fn flood_fill_with_walls(
+    start: Hex,
+    predicate: impl Fn(Hex) -> bool,
+    is_wall: impl Fn(GridEdge) -> bool,
+) -> Vec<Hex> {
+    Components::compute([start], predicate, unwalled(is_wall))
+        .into_groups()
+        .pop()
+        .unwrap_or_default()
+}
+
+/// Computes the connected [`Components`] of the coordinates matching
+/// `predicate`, starting from `coords`.
+///
+/// Every coordinate of `coords` matching `predicate` belongs to a component,
+/// which may extend outside of `coords` as long as `predicate` matches.
+/// Labels are assigned in `coords` order.
+///
+/// # Warning
+///
+/// The grid is infinite, so `predicate` must bound the components or the
+/// algorithm will never end.
+///
+/// > See [`connected_components_with_walls`] to separate components with
+/// > walls, and [`store_connected_components`] to use a [`HexStore`]
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::connected_components;
+///
+/// // Two islands
+/// let island = |h: Hex| h.distance_to(hex(-5, 0)) <= 2 || h.distance_to(hex(5, 0)) <= 1;
+/// let islands = connected_components(Hex::ZERO.range(10), island);
+/// assert_eq!(islands.len(), 2);
+/// assert_eq!(islands.component_of(hex(5, 0)).unwrap().len(), 7);
+/// assert!(islands.are_connected(hex(-5, 0), hex(-4, 1)));
+/// assert!(!islands.are_connected(hex(-5, 0), hex(5, 0)));
+/// assert_eq!(islands.label(Hex::ZERO), None);
+/// ```
+pub fn connected_components(
+    coords: impl IntoIterator<Item = Hex>,
+    predicate: impl Fn(Hex) -> bool,
+) -> Components {
+    Components::compute(coords, predicate, |h, dir| Some(h + dir))
+}
+
+/// Computes the connected [`Components`] of the coordinates matching
+/// `predicate`, starting from `coords`, considering the edges for which
+/// `is_wall` returns `true` as separators.
+///
+/// Both orientations of an edge are checked, so `is_wall` may only recognize
+/// one of them (See [`GridEdge::equivalent`]).
+///
+/// > See [`connected_components`] for more information
+#[cfg(feature = "grid")]
+pub fn connected_components_with_walls(
+    coords: impl IntoIterator<Item = Hex>,
+    predicate: impl Fn(Hex) -> bool,
+    is_wall: impl Fn(GridEdge) -> bool,
+) -> Components {
+    Components::compute(coords, predicate, unwalled(is_wall))
+}
+
+/// Computes the coordinates of `store` connected to `start` whose values
+/// match `predicate`.
+///
+/// Neighbors are resolved through [`HexStore::wrapped_coord`], so components
+/// of wrapping [`RectMap`](crate::storage::RectMap) cross the map seams.
+///
+/// > See [`flood_fill`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::{
+///     algorithms::store_flood_fill,
+///     storage::{HexStore, HexagonalMap},
+/// };
+///
+/// let map = HexagonalMap::new(Hex::ZERO, 5, |h| h.x > 0);
+/// let filled = store_flood_fill(&map, hex(1, 0), |_, v| *v);
+/// assert_eq!(filled.len(), map.iter().filter(|(_, v)| **v).count());
+/// ```
+pub fn store_flood_fill<T>(
+    store: &impl HexStore<T>,
+    start: Hex,
+    predicate: impl Fn(Hex, &T) -> bool,
+) -> Vec<Hex> {
+    let Some(start) = store.wrapped_coord(start) else {
+        return Vec::new();
+    };
+    Components::compute(
+        [start],
+        |h| store.get(h).is_some_and(|v| predicate(h, v)),
+        |h, dir| store.wrapped_coord(h + dir),
+    )
+    .into_groups()
+    .pop()
+    .unwrap_or_default()
+}
+
+/// Computes the connected [`Components`] of the coordinates of `store` whose
+/// values match `predicate`.
+///
+/// Neighbors are resolved through [`HexStore::wrapped_coord`], so components
+/// of wrapping [`RectMap`](crate::storage::RectMap) cross the map seams.
+///
+/// > See [`connected_components`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::{algorithms::store_connected_components, storage::HexagonalMap};
+///
+/// // Land and water
+/// let map = HexagonalMap::new(Hex::ZERO, 5, |h| h.x.abs() > 1);
+/// let islands = store_connected_components(&map, |_, land| *land);
+/// assert_eq!(islands.len(), 2);
+/// let lakes = store_connected_components(&map, |_, land| !*land);
+/// assert_eq!(lakes.len(), 1);
+/// ```
+pub fn store_connected_components<T>(
+    store: &impl HexStore<T>,
+    predicate: impl Fn(Hex, &T) -> bool,
+) -> Components {
+    Components::compute(
+        store.iter().map(|(h, _)| h),
+        |h| store.get(h).is_some_and(|v| predicate(h, v)),
+        |h, dir| store.wrapped_coord(h + dir),
+    )
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{
+        HexOrientation,
+        storage::{HexStore, RectMap, RectMetadata, WrapStrategy},
+    };
+    use glam::{IVec2, UVec2};
+
+    #[test]
+    fn components_partition() {
+        let predicate = |h: Hex| (h.x * 7 + h.y * 3).rem_euclid(5) != 0;
+        let components =
+            connected_components(Hex::ZERO.range(8), |h| h.ulength() <= 8 && predicate(h));
+        let mut total = 0;
+        for (label, group) in components.iter().enumerate() {
+            total += group.len();
+            for coord in group {
+                assert_eq!(components.label(*coord), Some(label));
+                assert_eq!(
+                    flood_fill(*coord, |h| h.ulength() <= 8 && predicate(h)).len(),
+                    group.len()
+                );
+            }
+            // No neighbor outside of the group matches
+            for coord in group {
+                for n in coord.all_neighbors() {
+                    if n.ulength() <= 8 && predicate(n) {
+                        assert_eq!(components.label(n), Some(label));
+                    }
+                }
+            }
+        }
+        assert_eq!(total, components.labels().len());
+    }
+
+    #[test]
+    #[cfg(feature = "grid")]
+    fn walls() {
+        // Vertical wall along the `x = 0` column
+        let is_wall = |e: GridEdge| e.origin.x == 0 && e.direction.into_hex().x > 0;
+        let area = |h: Hex| h.ulength() <= 4;
+        let components = connected_components_with_walls(Hex::ZERO.range(4), area, is_wall);
+        assert_eq!(components.len(), 2);
+        assert!(components.are_connected(Hex::new(-4, 0), Hex::ZERO));
+        assert!(!components.are_connected(Hex::ZERO, Hex::new(1, 0)));
+        // Walls are checked in both orientations
+        let filled = flood_fill_with_walls(Hex::new(1, 0), area, is_wall);
+        assert!(filled.iter().all(|h| h.x > 0));
+        assert_eq!(
+            filled.len(),
+            components.component_of(Hex::new(4, 0)).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn wrapping_store() {
+        let meta = RectMetadata::from_half_size([6, 4])
+            .with_orientation(HexOrientation::Pointy)
+            .with_wrap_strategies([WrapStrategy::Cycle, WrapStrategy::Clamp]);
+        // Two vertical strips touching the seam
+        let map = RectMap::new(meta.clone(), |h| {
+            let x = meta.hex_to_offset(h).x;
+            !(-4..=3).contains(&x)
+        });
+        let components = store_connected_components(&map, |_, v| *v);
+        assert_eq!(components.len(), 1);
+        let components = store_connected_components(&map, |_, v| !*v);
+        assert_eq!(components.len(), 1);
+        let filled = store_flood_fill(&map, Hex::new(-6, 0), |_, v| *v);
+        assert_eq!(filled.len(), map.values().filter(|v| **v).count());
+    }
+
+    #[test]
+    fn odd_wrapping_store() {
+        let strategies = [
+            [WrapStrategy::Cycle, WrapStrategy::Clamp],
+            [WrapStrategy::Clamp, WrapStrategy::Cycle],
+            [WrapStrategy::Cycle; 2],
+        ];
+        for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+            for wrap in strategies {
+                let meta = RectMetadata::from_start_dim(IVec2::new(-3, -2), UVec2::new(7, 5))
+                    .with_orientation(orientation)
+                    .with_wrap_strategies(wrap);
+                let map = RectMap::new(meta, |h| (h.x * 7 + h.y * 3).rem_euclid(5) < 2);
+                let components = store_connected_components(&map, |_, v| *v);
+                for (coord, label) in components.labels() {
+                    // Matching neighbors share the same label
+                    for n in coord.all_neighbors() {
+                        if let Some(n) = map.wrapped_coord(n).filter(|n| map[*n]) {
+                            assert_eq!(components.label(n), Some(*label));
+                        }
+                    }
+                    let filled = store_flood_fill(&map, *coord, |_, v| *v);
+                    assert_eq!(filled.len(), components.group(*label).unwrap().len());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use super::pathfinding::Node;
+use crate::{EdgeDirection, Hex, storage::HexStoreMap};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+use std::collections::BinaryHeap;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+
+/// Computes a *flow field* over a hexagonal `store`, giving for every stored
+/// coordinate the [`EdgeDirection`] of the next step towards the closest of
+/// the `goals`.
+///
+/// The result is a storage of the same kind as `store` (see
+/// [`HexStoreMap`]), containing `None` for the goals and for the
+/// coordinates which can't reach any goal.
+///
+/// Neighbors are resolved through
+/// [`HexStore::wrapped_coord`](crate::storage::HexStore::wrapped_coord), so on
+/// a [`RectMap`](crate::storage::RectMap) using
+/// [`WrapStrategy::Cycle`](crate::storage::WrapStrategy::Cycle) the flow
+/// wraps around the map: a direction may then point outside of the map, and
+/// the actual next coordinate is its wrapped neighbor.
+///
+/// # Arguments
+///
+/// * `store` - the map, containing the data used to compute costs
+/// * `goals` - the destination coordinates, goals outside of `store` are
+///   ignored
+/// * `cost` - function returning the cost to enter a coordinate given its
+///   stored value (`Some`), or `None` if the coordinate can't be entered
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::{algorithms::flow_field, storage::HexagonalMap};
+///
+/// // Map with a wall
+/// let map = HexagonalMap::new(Hex::ZERO, 10, |h| h.x == 2 && h.y < 5);
+/// let goal = hex(5, 0);
+/// let field = flow_field(&map, [goal], |_, wall| (!wall).then_some(1));
+/// assert_eq!(field[goal], None);
+/// assert_eq!(field[hex(4, 0)], Some(EdgeDirection::FLAT_SOUTH_EAST));
+/// // Follow the flow
+/// let mut coord = hex(-5, 0);
+/// while let Some(dir) = field[coord] {
+///     coord += dir;
+///     assert!(!map[coord]);
+/// }
+/// assert_eq!(coord, goal);
+/// ```
+pub fn flow_field<T, S>(
+    store: &S,
+    goals: impl IntoIterator<Item = Hex>,
+    cost: impl Fn(Hex, &T) -> Option<u32>,
+) -> S::Mapped<Option<EdgeDirection>>
+where
+    S: HexStoreMap<T>,
+{
+    let mut open = BinaryHeap::new();
+    let mut costs = HashMap::new();
+    let mut directions = HashMap::new();
+    for goal in goals.into_iter().filter_map(|g| store.wrapped_coord(g)) {
+        costs.insert(goal, 0_u32);
+        open.push(Node {
+            coord: goal,
+            score: 0,
+        });
+    }
+    while let Some(node) = open.pop() {
+        // Skip outdated entries
+        if costs[&node.coord] < node.score {
+            continue;
+        }
+        // Cost to enter the current coordinate from any neighbor
+        let Some(step) = store
+            .get(node.coord)
+            .and_then(|value| cost(node.coord, value))
+        else {
+            continue;
+        };
+        let neighbor_cost = node.score.saturating_add(step);
+        for dir in EdgeDirection::ALL_DIRECTIONS {
+            let Some(neighbor) = store.wrapped_coord(node.coord + dir) else {
+                continue;
+            };
+            if costs.get(&neighbor).is_none_or(|c| *c > neighbor_cost) {
+                costs.insert(neighbor, neighbor_cost);
+                directions.insert(neighbor, -dir);
+                open.push(Node {
+                    coord: neighbor,
+                    score: neighbor_cost,
+                });
+            }
+        }
+    }
+    store.map_values(|coord, _| directions.get(&coord).copied())
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{
+        HexOrientation, HexTopology,
+        algorithms::dijkstra_map,
+        storage::{HexStore, RectMap, RectMetadata, WrapStrategy},
+    };
+    use glam::{IVec2, UVec2};
+
+    #[test]
+    fn matches_dijkstra() {
+        let map: std::collections::HashMap<Hex, u32> = Hex::ZERO
+            .range(12)
+            .map(|h| (h, (h.x * 3 + h.y).unsigned_abs() % 4))
+            .collect();
+        let cost = |_: Hex, v: &u32| (*v != 0).then_some(*v);
+        let goals = [Hex::new(3, 3), Hex::new(-6, 2)];
+        let field = flow_field(&map, goals, cost);
+        // Reversed search: going from `b` to `a` costs entering `a`
+        let distances = dijkstra_map(goals, u32::MAX, |a, b| {
+            map.contains_key(&b).then(|| cost(a, &map[&a])).flatten()
+        });
+        for (&coord, &dir) in &field {
+            let Some(dir) = dir else {
+                assert!(goals.contains(&coord) || !distances.contains(coord));
+                continue;
+            };
+            let next = coord + dir;
+            assert_eq!(
+                distances.cost(coord).unwrap(),
+                distances.cost(next).unwrap() + cost(next, &map[&next]).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn wrapping() {
+        let meta = RectMetadata::from_half_size([10, 5])
+            .with_orientation(HexOrientation::Pointy)
+            .with_wrap_strategies([WrapStrategy::Cycle, WrapStrategy::Clamp]);
+        let map = RectMap::new(meta, |_| ());
+        // Opposite sides of the seam
+        let [goal, start] = [Hex::new(-10, 0), Hex::new(9, 0)];
+        assert!(map.get(goal).is_some() && map.get(start).is_some());
+        let field = flow_field(&map, [goal], |_, ()| Some(1));
+        assert_eq!(field[start], Some(EdgeDirection::POINTY_RIGHT));
+        assert_eq!(
+            map.wrapped_coord(start + EdgeDirection::POINTY_RIGHT),
+            Some(goal)
+        );
+        // Every coordinate reaches the goal
+        for start in map.meta().iter_hex() {
+            let mut coord = start;
+            let mut steps = 0;
+            while let Some(dir) = field[coord] {
+                coord = map.wrapped_coord(coord + dir).unwrap();
+                steps += 1;
+            }
+            assert_eq!(coord, goal);
+            // Without wrapping the right edge would be 19 steps away
+            assert!(steps <= 12);
+        }
+    }
+
+    #[test]
+    fn odd_wrapping() {
+        let strategies = [
+            [WrapStrategy::Cycle, WrapStrategy::Clamp],
+            [WrapStrategy::Clamp, WrapStrategy::Cycle],
+            [WrapStrategy::Cycle; 2],
+        ];
+        for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+            for wrap in strategies {
+                let meta = RectMetadata::from_start_dim(IVec2::new(-3, -2), UVec2::new(7, 5))
+                    .with_orientation(orientation)
+                    .with_wrap_strategies(wrap);
+                let map = RectMap::new(meta, |_| ());
+                let goal = map.meta().iter_hex().nth(8).unwrap();
+                let field = flow_field(&map, [goal], |_, ()| Some(1));
+                // Following the field takes the shortest path to the goal
+                for start in map.meta().iter_hex() {
+                    let mut coord = start;
+                    let mut steps = 0_u32;
+                    while let Some(dir) = field[coord] {
+                        coord = map.wrapped_coord(coord + dir).unwrap();
+                        steps += 1;
+                        assert!(steps as usize <= map.len());
+                    }
+                    assert_eq!(coord, goal);
+                    assert_eq!(steps, map.meta().distance(start, goal));
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,18 @@
-use crate::{EdgeDirection, Hex};
+use crate::{EdgeDirection, Hex, HexLayout, HexOrientation, HexTopology};
 #[cfg(feature = "bevy_platform")]
-use bevy_platform::collections::HashSet;
+use bevy_platform::collections::{HashMap, HashSet};
+use glam::Vec2;
 #[cfg(not(feature = "bevy_platform"))]
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::{PI, TAU};
 
 /// Computes a field of view around `coord` in a given `range`
 ///
 /// This algorithm takes in account coordinates *visibility* through the
 /// `blocking` argument. (*Blocking* coordinates should return `true`)
 ///
+/// > See [`shadowcast_fov`] for a faster and symmetric field of view
+///
 /// # Examples
 ///
 /// - Compute field of view with no boundaries and some blocking tiles
@@ -27,10 +31,42 @@ use std::collections::HashSet;
 /// let fov = range_fov(pos, range, |h| blocking_coords.contains(&h));
 /// ```
 pub fn range_fov(coord: Hex, range: u32, blocking: impl Fn(Hex) -> bool) -> HashSet<Hex> {
-    coord
-        .ring(range)
-        .flat_map(|target| coord.line_to(target).take_while(|h| !blocking(*h)))
-        .collect()
+    let mut res = HashSet::default();
+    range_fov_into(coord, range, blocking, &mut res);
+    res
+}
+
+/// Computes a field of view around `coord` in a given `range`, writing the
+/// visible coordinates into `output`
+///
+/// Coordinates may be written multiple times, which suits sets like
+/// [`HexBitSet`](crate::storage::HexBitSet) and allows reusing allocations
+/// across calls.
+///
+/// > See [`range_fov`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::HexBitSet};
+/// use hexx::algorithms::range_fov_into;
+///
+/// let mut visible = HexBitSet::hexagonal(HexBounds::new(Hex::ZERO, 20));
+/// range_fov_into(Hex::ZERO, 10, |h| h == hex(2, 0), &mut visible);
+/// assert!(visible.contains(hex(1, 0)));
+/// assert!(!visible.contains(hex(3, 0)));
+/// ```
+pub fn range_fov_into(
+    coord: Hex,
+    range: u32,
+    blocking: impl Fn(Hex) -> bool,
+    output: &mut impl Extend<Hex>,
+) {
+    output.extend(
+        coord
+            .ring(range)
+            .flat_map(|target| coord.line_to(target).take_while(|h| !blocking(*h))),
+    );
 }
 
 /// Computes a field of view around `coord` in a given `range` towards
@@ -39,6 +75,8 @@ pub fn range_fov(coord: Hex, range: u32, blocking: impl Fn(Hex) -> bool) -> Hash
 /// This algorithm takes in account coordinates *visibility* through the
 /// `blocking` argument. (*Blocking* coordinates should return `true`)
 ///
+/// > See [`cone_fov`] for a symmetric field of view with arbitrary angles
+///
 /// # Examples
 ///
 /// - Compute drectional field of view with no boundaries and some blocking
@@ -74,3 +112,465 @@ pub fn directional_fov(
         .flat_map(|target| coord.line_to(target).take_while(|h| !blocking(*h)))
         .collect()
 }
+
+/// Sorted, disjoint angular intervals in `[0, TAU]` used for shadowcasting
+#[derive(Debug, Default)]
+struct Shadows(Vec<[f32; 2]>);
+
+impl Shadows {
+    /// Splits a `[start, end]` interval, with `start` in `[0, TAU)`, in
+    /// intervals contained in `[0, TAU]`
+    fn normalized([start, end]: [f32; 2]) -> impl Iterator<Item = [f32; 2]> {
+        let second = (end > TAU).then_some([0.0, end - TAU]);
+        std::iter::once([start, end.min(TAU)]).chain(second)
+    }
+
+    fn insert(&mut self, interval: [f32; 2]) {
+        for [start, end] in Self::normalized(interval) {
+            let idx = self.0.partition_point(|s| s[1] < start - ANGLE_EPSILON);
+            let mut merged = [start, end];
+            while let Some(s) = self
+                .0
+                .get(idx)
+                .filter(|s| s[0] <= merged[1] + ANGLE_EPSILON)
+            {
+                merged = [merged[0].min(s[0]), merged[1].max(s[1])];
+                self.0.remove(idx);
+            }
+            self.0.insert(idx, merged);
+        }
+    }
+
+    /// Is `angle` strictly inside a shadow
+    fn contains(&self, angle: f32) -> bool {
+        let inside = |angle: f32| {
+            let idx = self.0.partition_point(|s| s[1] <= angle + ANGLE_EPSILON);
+            self.0
+                .get(idx)
+                .is_some_and(|s| s[0] < angle - ANGLE_EPSILON)
+        };
+        // Shadows split at `0` and `TAU` are contiguous
+        let wraps = self
+            .0
+            .first()
+            .is_some_and(|s| s[0] <= ANGLE_EPSILON && s[1] > ANGLE_EPSILON)
+            && self
+                .0
+                .last()
+                .is_some_and(|s| s[1] >= TAU - ANGLE_EPSILON && s[0] < TAU - ANGLE_EPSILON);
+        inside(angle) || (wraps && angle.min(TAU - angle) < ANGLE_EPSILON)
+    }
+
+    /// Ratio of `interval` which is not in a shadow
+    fn visible_ratio(&self, interval: [f32; 2]) -> f32 {
+        let len = interval[1] - interval[0];
+        let covered: f32 = Self::normalized(interval)
+            .map(|[start, end]| {
+                self.0
+                    .iter()
+                    .map(|s| (s[1].min(end) - s[0].max(start)).max(0.0))
+                    .sum::<f32>()
+            })
+            .sum();
+        let ratio = 1.0 - covered / len;
+        if ratio <= ANGLE_EPSILON { 0.0 } else { ratio }
+    }
+
+    fn is_full(&self) -> bool {
+        self.0
+            .first()
+            .is_some_and(|s| s[0] <= ANGLE_EPSILON && s[1] >= TAU - ANGLE_EPSILON)
+    }
+}
+
+const ANGLE_EPSILON: f32 = 1e-5;
+const SHAPE_EPSILON: f32 = 1e-3;
+
+/// Maps `angle` in `[0, TAU)`
+fn positive_angle(angle: f32) -> f32 {
+    let angle = angle.rem_euclid(TAU);
+    // `rem_euclid` may return `TAU` for small negative values
+    if angle >= TAU { angle - TAU } else { angle }
+}
+
+/// Angular extent of a shadowcasting cone
+#[derive(Debug, Clone, Copy)]
+struct Cone {
+    angle: f32,
+    half_width: f32,
+}
+
+impl Cone {
+    fn contains(self, angle: f32) -> bool {
+        let delta = (angle - self.angle + PI).rem_euclid(TAU) - PI;
+        delta.abs() <= self.half_width + ANGLE_EPSILON
+    }
+}
+
+/// Local geometry of a hexagon relative to the viewer
+struct HexShape {
+    center: Vec2,
+    /// Angle of the center in `[0, TAU)`
+    angle: f32,
+    /// Angular interval of the hexagon, with `start` in `[0, TAU)`
+    interval: [f32; 2],
+}
+
+impl HexShape {
+    fn new(layout: &HexLayout, coord: Hex) -> Self {
+        let center = layout.hex_to_world_pos(coord);
+        let angle = positive_angle(center.to_angle());
+        let [min, max] = layout
+            .center_aligned_hex_corners()
+            .into_iter()
+            .map(|c| Vec2::from_angle(-angle).rotate(center + c).to_angle())
+            .fold([0.0_f32, 0.0_f32], |[min, max], a| [min.min(a), max.max(a)]);
+        let start = positive_angle(angle + min);
+        Self {
+            center,
+            angle,
+            interval: [start, start + max - min],
+        }
+    }
+
+    /// Checks if the segment from the viewer to `target` crosses the inside of
+    /// the hexagon
+    fn blocks(&self, layout: &HexLayout, target: Vec2) -> bool {
+        let corners = layout.center_aligned_hex_corners().map(|c| c + self.center);
+        let len = target.length();
+        let dir = target / len;
+        // Entry distance along the segment, using the separating axis of every
+        // edge
+        let mut t_min = 0.0_f32;
+        let mut t_max = len;
+        for (i, a) in corners.iter().enumerate() {
+            let b = corners[(i + 1) % 6];
+            let outward = (*a + b) / 2.0 - self.center;
+            let denom = dir.dot(outward);
+            // The hexagon is slightly shrunk to ignore segments along its edges
+            let dist = SHAPE_EPSILON.mul_add(-outward.length(), a.dot(outward));
+            if denom.abs() < f32::EPSILON {
+                if dist <= 0.0 {
+                    return false;
+                }
+                continue;
+            }
+            let t = dist / denom;
+            if denom > 0.0 {
+                t_max = t_max.min(t);
+            } else {
+                t_min = t_min.max(t);
+            }
+        }
+        t_max > t_min
+    }
+}
+
+fn shadowcast(
+    coord: Hex,
+    range: u32,
+    orientation: HexOrientation,
+    cone: Option<Cone>,
+    blocking: impl Fn(Hex) -> bool,
+) -> HashMap<Hex, f32> {
+    let layout = HexLayout::new(orientation);
+    let mut res = HashMap::new();
+    res.insert(coord, 1.0);
+    let mut shadows = Shadows::default();
+    for radius in 1..=range {
+        if shadows.is_full() {
+            break;
+        }
+        let mut new_shadows = Vec::new();
+        for hex in coord.ring(radius) {
+            let local = hex - coord;
+            let shape = HexShape::new(&layout, local);
+            let is_blocking = blocking(hex);
+            // Blocking coordinates outside of the cone still cast shadows
+            if is_blocking {
+                new_shadows.push(shape.interval);
+            }
+            if cone.is_some_and(|c| !c.contains(shape.angle)) {
+                continue;
+            }
+            let ratio = shadows.visible_ratio(shape.interval);
+            if is_blocking {
+                if ratio > 0.0 {
+                    res.insert(hex, ratio);
+                }
+                continue;
+            }
+            if shadows.contains(shape.angle) {
+                continue;
+            }
+            // Blocking coordinates of the same ring may hide the center
+            let hidden = local.all_neighbors().into_iter().any(|n| {
+                n.ulength() == radius
+                    && blocking(n + coord)
+                    && HexShape::new(&layout, n).blocks(&layout, shape.center)
+            });
+            if !hidden {
+                res.insert(hex, ratio);
+            }
+        }
+        for interval in new_shadows {
+            shadows.insert(interval);
+        }
+    }
+    res
+}
+
+/// Computes a symmetric field of view around `coord` in a given `range` using
+/// *shadowcasting*.
+///
+/// This algorithm takes in account coordinates *visibility* through the
+/// `blocking` argument. (*Blocking* coordinates should return `true`)
+///
+/// Unlike [`range_fov`], which casts lines to every coordinate of the outer
+/// ring, each coordinate is visited only once and the result is *symmetric*:
+/// if a non blocking coordinate `a` sees `b` then `b` sees `a`.
+///
+/// * Non blocking coordinates are visible if their center is visible
+/// * Blocking coordinates are visible if any part of them is visible
+///
+/// > See [`shadowcast_fov_partial`] to retrieve the visible ratio of each
+/// > coordinate
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashSet;
+/// use hexx::algorithms::shadowcast_fov;
+///
+/// let pos = hex(0, 0);
+/// let range = 10;
+/// let blocking_coords: HashSet<Hex> = [hex(2, 0)].into();
+/// let fov = shadowcast_fov(pos, range, |h| blocking_coords.contains(&h));
+/// assert!(fov.contains(&hex(2, 0)));
+/// assert!(!fov.contains(&hex(3, 0)));
+/// ```
+pub fn shadowcast_fov(coord: Hex, range: u32, blocking: impl Fn(Hex) -> bool) -> HashSet<Hex> {
+    let mut res = HashSet::default();
+    shadowcast_fov_into(coord, range, blocking, &mut res);
+    res
+}
+
+/// Computes a symmetric field of view around `coord` in a given `range` using
+/// *shadowcasting*, writing the visible coordinates into `output`
+///
+/// > See [`shadowcast_fov`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::ChunkedHexBitSet};
+/// use hexx::algorithms::shadowcast_fov_into;
+///
+/// // Accumulates the explored coordinates
+/// let mut explored = ChunkedHexBitSet::default();
+/// shadowcast_fov_into(Hex::ZERO, 5, |h| h == hex(2, 0), &mut explored);
+/// shadowcast_fov_into(hex(10, 0), 5, |h| h == hex(2, 0), &mut explored);
+/// assert!(explored.contains(hex(2, 0)));
+/// assert!(explored.contains(hex(15, 0)));
+/// assert!(!explored.contains(hex(3, 0)));
+/// ```
+pub fn shadowcast_fov_into(
+    coord: Hex,
+    range: u32,
+    blocking: impl Fn(Hex) -> bool,
+    output: &mut impl Extend<Hex>,
+) {
+    output.extend(shadowcast_fov_partial(coord, range, blocking).into_keys());
+}
+
+/// Computes a symmetric field of view around `coord` in a given `range` using
+/// *shadowcasting* in the given `topology`, allowing to see across the seams
+/// of wrapping maps.
+///
+/// `blocking` is only called with canonical coordinates, and coordinates
+/// outside of the world are considered blocking. The returned coordinates are
+/// canonical.
+///
+/// > See [`shadowcast_fov`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::shadowcast_fov_in;
+///
+/// let bounds = HexBounds::new(Hex::ZERO, 5);
+/// let fov = shadowcast_fov_in(bounds, hex(5, 0), 2, |h| h == hex(-5, 5));
+/// // Coordinates on the other side of the seam are visible
+/// assert!(fov.contains(&hex(-5, 5)));
+/// assert!(fov.contains(&hex(-4, 4)));
+/// // But hidden behind the blocking coordinate
+/// assert!(!fov.contains(&hex(-4, 5)));
+/// ```
+pub fn shadowcast_fov_in(
+    topology: impl HexTopology,
+    coord: Hex,
+    range: u32,
+    blocking: impl Fn(Hex) -> bool,
+) -> HashSet<Hex> {
+    shadowcast(coord, range, HexOrientation::default(), None, |h| {
+        topology.canonical(h).is_none_or(&blocking)
+    })
+    .into_keys()
+    .filter_map(|h| topology.canonical(h))
+    .collect()
+}
+
+/// Computes a symmetric field of view around `coord` in a given `range` using
+/// *shadowcasting*, with the visible ratio of every visible coordinate.
+///
+/// The returned ratio is in `]0.0, 1.0]` and represents how much of the
+/// coordinate is visible from `coord`, which can be used for partial lighting
+/// or soft fog of war.
+///
+/// > See [`shadowcast_fov`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::shadowcast_fov_partial;
+///
+/// let fov = shadowcast_fov_partial(Hex::ZERO, 5, |h| h == hex(2, 0));
+/// assert_eq!(fov[&hex(1, 0)], 1.0);
+/// assert!(fov[&hex(3, -1)] < 1.0);
+/// ```
+pub fn shadowcast_fov_partial(
+    coord: Hex,
+    range: u32,
+    blocking: impl Fn(Hex) -> bool,
+) -> HashMap<Hex, f32> {
+    shadowcast(coord, range, HexOrientation::default(), None, blocking)
+}
+
+/// Computes a field of view around `coord` in a given `range` using
+/// *shadowcasting*, restricted to a cone of arbitrary `angle` and `width`.
+///
+/// Coordinates are included if their center is in the cone.
+///
+/// # Arguments
+///
+/// * `coord` - the viewer coordinate
+/// * `range` - the view distance
+/// * `orientation` - the hexagon orientation used to interpret `angle`, see
+///   [`EdgeDirection::angle`]
+/// * `angle` - the cone direction in radians
+/// * `width` - the full cone aperture in radians
+/// * `blocking` - returns `true` for coordinates blocking the view
+///
+/// > See [`shadowcast_fov`] for more information
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashSet;
+/// use hexx::algorithms::cone_fov;
+///
+/// let dir = EdgeDirection::POINTY_RIGHT;
+/// let angle = dir.angle(HexOrientation::Pointy);
+/// let fov = cone_fov(Hex::ZERO, 10, HexOrientation::Pointy, angle, 0.5, |_| false);
+/// assert!(fov.contains(&(dir * 10)));
+/// assert!(!fov.contains(&(-dir * 10)));
+/// ```
+pub fn cone_fov(
+    coord: Hex,
+    range: u32,
+    orientation: HexOrientation,
+    angle: f32,
+    width: f32,
+    blocking: impl Fn(Hex) -> bool,
+) -> HashSet<Hex> {
+    cone_fov_partial(coord, range, orientation, angle, width, blocking)
+        .into_keys()
+        .collect()
+}
+
+/// Same as [`cone_fov`] but with the visible ratio of every visible
+/// coordinate.
+///
+/// > See [`shadowcast_fov_partial`] for more information
+pub fn cone_fov_partial(
+    coord: Hex,
+    range: u32,
+    orientation: HexOrientation,
+    angle: f32,
+    width: f32,
+    blocking: impl Fn(Hex) -> bool,
+) -> HashMap<Hex, f32> {
+    let cone = Cone {
+        angle: positive_angle(angle),
+        half_width: width.abs() / 2.0,
+    };
+    shadowcast(coord, range, orientation, Some(cone), blocking)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo random blocking coordinates
+    fn blocking(hex: Hex) -> bool {
+        hex != Hex::ZERO && (hex.x * 7 + hex.y * 13 + hex.x * hex.y).rem_euclid(5) == 0
+    }
+
+    #[test]
+    fn shadowcast_is_symmetric() {
+        let range = 6;
+        let coords: Vec<_> = Hex::ZERO.range(range).filter(|h| !blocking(*h)).collect();
+        let fovs: HashMap<_, _> = coords
+            .iter()
+            .map(|c| (*c, shadowcast_fov(*c, 2 * range, blocking)))
+            .collect();
+        for a in &coords {
+            for b in &coords {
+                assert_eq!(fovs[a].contains(b), fovs[b].contains(a), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn shadowcast_respects_blockers() {
+        let layout = HexLayout::new(HexOrientation::default());
+        let fov = shadowcast_fov_partial(Hex::ZERO, 10, blocking);
+        for (coord, ratio) in fov {
+            assert!(ratio > 0.0 && ratio <= 1.0);
+            if blocking(coord) || coord == Hex::ZERO {
+                continue;
+            }
+            let target = layout.hex_to_world_pos(coord);
+            for blocker in Hex::ZERO.range(10).filter(|h| blocking(*h)) {
+                let shape = HexShape::new(&layout, blocker);
+                assert!(!shape.blocks(&layout, target), "{coord:?} {blocker:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn shadowcast_without_blockers() {
+        let fov = shadowcast_fov_partial(Hex::ZERO, 10, |_| false);
+        assert_eq!(fov.len(), Hex::range_count(10) as usize);
+        assert!(fov.values().all(|r| (*r - 1.0).abs() < f32::EPSILON));
+    }
+
+    #[test]
+    fn cone_matches_direction() {
+        for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+            for dir in EdgeDirection::ALL_DIRECTIONS {
+                let angle = dir.angle(orientation);
+                let fov = cone_fov(Hex::ZERO, 8, orientation, angle, 0.1, |_| false);
+                let expected: HashSet<_> = (0..=8).map(|i| dir * i).collect();
+                assert_eq!(fov, expected);
+            }
+        }
+        // Full cone
+        let fov = cone_fov(Hex::ZERO, 8, HexOrientation::Flat, 1.0, TAU, blocking);
+        assert_eq!(fov, shadowcast_fov(Hex::ZERO, 8, blocking));
+    }
+}
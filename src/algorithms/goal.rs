@@ -0,0 +1,271 @@
+use crate::{Hex, HexBounds, HexTopology};
+
+/// Destination of a pathfinding search, see [`AStar`](super::AStar).
+///
+/// Implemented for:
+/// - [`Hex`], a single destination
+/// - [`HexBounds`], any coordinate in bounds
+/// - Slices, arrays, [`Vec`] and hash sets of [`Hex`], any of the coordinates
+/// - [`GoalPredicate`], any coordinate matching a predicate
+/// - References to any of the above
+pub trait PathGoal {
+    /// Returns `true` if `coord` is a valid destination
+    fn is_goal(&self, coord: Hex) -> bool;
+
+    /// Estimates the distance from `coord` to the closest destination.
+    ///
+    /// The estimate should never be greater than the real distance
+    fn estimate(&self, coord: Hex) -> u32;
+
+    /// Estimates the distance from `coord` to the closest destination in the
+    /// given `topology`, taking wrapping in account.
+    ///
+    /// The default implementation ignores `topology` and calls
+    /// [`Self::estimate`], which may overestimate distances on wrapping maps
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        let _ = topology;
+        self.estimate(coord)
+    }
+
+    /// Returns `true` if no destination is `included` in the search, allowing
+    /// to return early
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        let _ = included;
+        false
+    }
+}
+
+impl PathGoal for Hex {
+    #[inline]
+    fn is_goal(&self, coord: Self) -> bool {
+        *self == coord
+    }
+
+    #[inline]
+    fn estimate(&self, coord: Self) -> u32 {
+        coord.unsigned_distance_to(*self)
+    }
+
+    #[inline]
+    fn topology_estimate(&self, coord: Self, topology: &impl HexTopology) -> u32 {
+        topology.distance(coord, *self)
+    }
+
+    fn is_excluded(&self, included: impl Fn(Self) -> bool) -> bool {
+        !included(*self)
+    }
+}
+
+impl PathGoal for HexBounds {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        self.is_in_bounds(coord)
+    }
+
+    #[inline]
+    fn estimate(&self, coord: Hex) -> u32 {
+        self.center
+            .unsigned_distance_to(coord)
+            .saturating_sub(self.radius)
+    }
+
+    #[inline]
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        topology
+            .distance(self.center, coord)
+            .saturating_sub(self.radius)
+    }
+}
+
+impl PathGoal for [Hex] {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        self.contains(&coord)
+    }
+
+    fn estimate(&self, coord: Hex) -> u32 {
+        self.iter()
+            .map(|h| h.unsigned_distance_to(coord))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        self.iter()
+            .map(|h| topology.distance(coord, *h))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        !self.iter().any(|h| included(*h))
+    }
+}
+
+impl<const N: usize> PathGoal for [Hex; N] {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        self.as_slice().is_goal(coord)
+    }
+
+    #[inline]
+    fn estimate(&self, coord: Hex) -> u32 {
+        self.as_slice().estimate(coord)
+    }
+
+    #[inline]
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        self.as_slice().topology_estimate(coord, topology)
+    }
+
+    #[inline]
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        self.as_slice().is_excluded(included)
+    }
+}
+
+impl PathGoal for Vec<Hex> {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        self.as_slice().is_goal(coord)
+    }
+
+    #[inline]
+    fn estimate(&self, coord: Hex) -> u32 {
+        self.as_slice().estimate(coord)
+    }
+
+    #[inline]
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        self.as_slice().topology_estimate(coord, topology)
+    }
+
+    #[inline]
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        self.as_slice().is_excluded(included)
+    }
+}
+
+impl<S: std::hash::BuildHasher> PathGoal for std::collections::HashSet<Hex, S> {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        self.contains(&coord)
+    }
+
+    fn estimate(&self, coord: Hex) -> u32 {
+        self.iter()
+            .map(|h| h.unsigned_distance_to(coord))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        self.iter()
+            .map(|h| topology.distance(coord, *h))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        !self.iter().any(|h| included(*h))
+    }
+}
+
+#[cfg(feature = "bevy_platform")]
+impl<S: core::hash::BuildHasher> PathGoal for bevy_platform::collections::HashSet<Hex, S> {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        self.contains(&coord)
+    }
+
+    fn estimate(&self, coord: Hex) -> u32 {
+        self.iter()
+            .map(|h| h.unsigned_distance_to(coord))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        self.iter()
+            .map(|h| topology.distance(coord, *h))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        !self.iter().any(|h| included(*h))
+    }
+}
+
+impl<G: PathGoal + ?Sized> PathGoal for &G {
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        (**self).is_goal(coord)
+    }
+
+    #[inline]
+    fn estimate(&self, coord: Hex) -> u32 {
+        (**self).estimate(coord)
+    }
+
+    #[inline]
+    fn topology_estimate(&self, coord: Hex, topology: &impl HexTopology) -> u32 {
+        (**self).topology_estimate(coord, topology)
+    }
+
+    #[inline]
+    fn is_excluded(&self, included: impl Fn(Hex) -> bool) -> bool {
+        (**self).is_excluded(included)
+    }
+}
+
+/// [`PathGoal`] matching any coordinate for which the inner predicate returns
+/// `true`.
+///
+/// As no heuristic can be computed from a predicate, the search will behave
+/// like a Dijkstra search unless a custom heuristic is provided.
+///
+/// Neither the estimate nor the search is bounded: if no reachable
+/// coordinate matches the predicate, the search expands forever on unbounded
+/// maps. Always bound the search with
+/// [`AStar::with_max_cost`](super::AStar::with_max_cost) or
+/// [`AStar::with_max_expanded_nodes`](super::AStar::with_max_expanded_nodes)
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::{AStar, GoalPredicate};
+///
+/// // Any coordinate adjacent to an enemy
+/// let enemy = hex(4, -8);
+/// let goal = GoalPredicate(|h: Hex| h.unsigned_distance_to(enemy) == 1);
+/// let path = AStar::new(Hex::ZERO, goal, |_, _| Some(1))
+///     .with_max_cost(50)
+///     .find()
+///     .unwrap();
+/// assert_eq!(path.cost, 7);
+/// // Unreachable predicate, the search gives up at the bound
+/// let goal = GoalPredicate(|_: Hex| false);
+/// let path = AStar::new(Hex::ZERO, goal, |_, _| Some(1))
+///     .with_max_expanded_nodes(1_000)
+///     .find();
+/// assert!(path.is_none());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GoalPredicate<F>(pub F);
+
+impl<F> PathGoal for GoalPredicate<F>
+where
+    F: Fn(Hex) -> bool,
+{
+    #[inline]
+    fn is_goal(&self, coord: Hex) -> bool {
+        (self.0)(coord)
+    }
+
+    #[inline]
+    fn estimate(&self, _coord: Hex) -> u32 {
+        0
+    }
+}
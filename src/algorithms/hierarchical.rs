@@ -0,0 +1,510 @@
+use super::{
+    AStar, HexPath, dijkstra_map,
+    pathfinding::{Node, reconstruct_path},
+};
+use crate::Hex;
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+use std::collections::BinaryHeap;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+
+/// Abstract graph of a single chunk
+#[derive(Debug, Clone, Default)]
+struct ChunkGraph {
+    /// Outgoing edges of every portal coordinate of the chunk, either to
+    /// another portal of the chunk or through a portal to a neighboring chunk
+    edges: HashMap<Hex, Vec<(Hex, u32)>>,
+}
+
+/// Hierarchical pathfinding (*HPA star*) on large maps.
+///
+/// The world is partitioned in hexagonal chunks of a given radius, using
+/// [`Hex::to_lower_res`]. For every pair of adjacent chunks, each contiguous
+/// section of their shared border which is passable in both directions
+/// defines an entrance, represented
+/// by a single *portal*: a pair of neighbor coordinates on both sides of the
+/// border. Portals of a chunk are connected through precomputed paths inside
+/// the chunk, building an abstract graph which is much smaller than the
+/// coordinate grid.
+///
+/// Long range queries search the abstract graph first and then refine the
+/// result into a coordinate path, chunk by chunk. The resulting paths are
+/// close to optimal but not guaranteed to be the cheapest.
+///
+/// Chunk graphs are computed lazily when a search reaches them, or eagerly
+/// through [`Self::precompute`], and cached until invalidated.
+///
+/// # Cost function
+///
+/// The `cost` parameter of every method follows the same convention as
+/// [`a_star`](super::a_star): it should give the cost to go from a coordinate
+/// to its neighbor (`Some`) or indicate the neighbor is not included in the
+/// search (`None`). Costs should be at least 1 for the distance heuristic to
+/// be admissible.
+///
+/// The cached graphs are only valid for a given `cost` function, so
+/// whenever costs change the affected coordinates or chunks should be
+/// invalidated with [`Self::invalidate`] or [`Self::invalidate_chunk`].
+///
+/// # Search bound
+///
+/// Chunks are explored as the search reaches them, so on unbounded maps an
+/// unreachable goal is searched forever. Bound the cost function to the map
+/// or set a maximum amount of expanded portals through
+/// [`Self::with_max_expanded_nodes`].
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashSet;
+/// use hexx::algorithms::HierarchicalPathfinder;
+///
+/// let mut walls: HashSet<Hex> = (-20..=20).map(|y| hex(5, y)).collect();
+/// walls.remove(&hex(5, 12));
+///
+/// let mut pathfinder = HierarchicalPathfinder::new(4);
+/// let path = pathfinder
+///     .find(hex(0, 0), hex(10, 0), |_, b| {
+///         (b.ulength() <= 40 && !walls.contains(&b)).then_some(1)
+///     })
+///     .unwrap();
+/// assert!(path.coords.contains(&hex(5, 12)));
+///
+/// // The entrance gets closed
+/// walls.insert(hex(5, 12));
+/// pathfinder.invalidate(hex(5, 12));
+/// let path = pathfinder
+///     .find(hex(0, 0), hex(10, 0), |_, b| {
+///         (b.ulength() <= 40 && !walls.contains(&b)).then_some(1)
+///     })
+///     .unwrap();
+/// assert!(!path.coords.contains(&hex(5, 12)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HierarchicalPathfinder {
+    chunk_radius: u32,
+    chunks: HashMap<Hex, ChunkGraph>,
+    max_expanded_nodes: Option<usize>,
+}
+
+impl HierarchicalPathfinder {
+    /// Creates a new pathfinder using chunks of the given `chunk_radius`
+    ///
+    /// > See [`Hex::to_lower_res`]
+    #[must_use]
+    pub fn new(chunk_radius: u32) -> Self {
+        Self {
+            chunk_radius,
+            chunks: HashMap::default(),
+            max_expanded_nodes: None,
+        }
+    }
+
+    /// Specify a maximum amount of abstract nodes to expand in
+    /// [`Self::find`] before giving up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// use hexx::algorithms::HierarchicalPathfinder;
+    ///
+    /// let goal = hex(30, 0);
+    /// // The goal is surrounded by walls on an unbounded map
+    /// let cost = |_, b: Hex| (b.unsigned_distance_to(goal) != 1).then_some(1);
+    /// let mut pathfinder = HierarchicalPathfinder::new(4).with_max_expanded_nodes(1_000);
+    /// assert!(pathfinder.find(Hex::ZERO, goal, cost).is_none());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_max_expanded_nodes(mut self, max_expanded_nodes: usize) -> Self {
+        self.max_expanded_nodes = Some(max_expanded_nodes);
+        self
+    }
+
+    /// Returns the radius of the chunks
+    #[inline]
+    #[must_use]
+    pub const fn chunk_radius(&self) -> u32 {
+        self.chunk_radius
+    }
+
+    /// Returns the chunk containing `coord`
+    #[inline]
+    #[must_use]
+    pub fn chunk_of(&self, coord: Hex) -> Hex {
+        coord.to_lower_res(self.chunk_radius)
+    }
+
+    /// Returns `true` if the abstract graph of `chunk` is computed
+    #[inline]
+    #[must_use]
+    pub fn is_cached(&self, chunk: Hex) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    /// Returns the portal coordinates of `chunk`, if its abstract graph is
+    /// computed
+    pub fn portals(&self, chunk: Hex) -> impl Iterator<Item = Hex> + '_ {
+        self.chunks
+            .get(&chunk)
+            .into_iter()
+            .flat_map(|graph| graph.edges.keys().copied())
+    }
+
+    /// Computes and caches the abstract graph of the given `chunks`
+    pub fn precompute(
+        &mut self,
+        chunks: impl IntoIterator<Item = Hex>,
+        cost: impl Fn(Hex, Hex) -> Option<u32>,
+    ) {
+        for chunk in chunks {
+            self.chunk_graph(chunk, &cost);
+        }
+    }
+
+    /// Invalidates the cached graphs after a cost change on `coord`.
+    ///
+    /// > See [`Self::invalidate_chunk`]
+    pub fn invalidate(&mut self, coord: Hex) {
+        self.invalidate_chunk(self.chunk_of(coord));
+    }
+
+    /// Invalidates the cached graph of `chunk` and of its neighbors, as their
+    /// shared portals may change.
+    ///
+    /// Invalidated graphs are recomputed lazily
+    pub fn invalidate_chunk(&mut self, chunk: Hex) {
+        self.chunks.remove(&chunk);
+        for neighbor in chunk.all_neighbors() {
+            self.chunks.remove(&neighbor);
+        }
+    }
+
+    /// Clears every cached graph
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    /// Computes a path from `start` to `goal`.
+    ///
+    /// Returns `None` if `goal` can't be reached, or if the maximum amount of
+    /// expanded nodes is reached (See [`Self::with_max_expanded_nodes`])
+    ///
+    /// # Note
+    ///
+    /// Without a bounded `cost` function or a maximum amount of expanded
+    /// nodes, this never returns when `goal` is unreachable
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - start node
+    /// * `goal` - destination node
+    /// * `cost` - cost function taking a node pair (`a` -> `b`) and returning
+    ///   the logical cost to go from `a` to `b`
+    pub fn find(
+        &mut self,
+        start: Hex,
+        goal: Hex,
+        cost: impl Fn(Hex, Hex) -> Option<u32>,
+    ) -> Option<HexPath> {
+        if start == goal {
+            return Some(HexPath {
+                coords: vec![start],
+                cost: 0,
+            });
+        }
+        let start_chunk = self.chunk_of(start);
+        let goal_chunk = self.chunk_of(goal);
+        // Temporary edges from `start` to the portals of its chunk
+        let from_start = dijkstra_map(
+            [start],
+            u32::MAX,
+            Self::chunk_cost(self.chunk_radius, start_chunk, &cost),
+        );
+        // Temporary edges from the portals of the goal chunk to `goal`
+        let radius = self.chunk_radius;
+        let to_goal = dijkstra_map([goal], u32::MAX, |a, b| {
+            (b.to_lower_res(radius) == goal_chunk)
+                .then(|| cost(b, a))
+                .flatten()
+        });
+
+        let mut open = BinaryHeap::new();
+        let mut costs = HashMap::new();
+        let mut came_from = HashMap::new();
+        costs.insert(start, 0_u32);
+        open.push(Node {
+            coord: start,
+            score: start.unsigned_distance_to(goal),
+        });
+        let mut expanded = 0;
+        while let Some(node) = open.pop() {
+            if node.coord == goal {
+                break;
+            }
+            let current_cost = costs[&node.coord];
+            // Skip outdated entries
+            if node.score > current_cost + node.coord.unsigned_distance_to(goal) {
+                continue;
+            }
+            expanded += 1;
+            if self.max_expanded_nodes.is_some_and(|max| expanded > max) {
+                return None;
+            }
+            let mut successors = self
+                .chunk_graph(self.chunk_of(node.coord), &cost)
+                .edges
+                .get(&node.coord)
+                .cloned()
+                .unwrap_or_default();
+            if node.coord == start {
+                let portals = self.chunk_graph(start_chunk, &cost).edges.keys();
+                successors.extend(portals.filter_map(|p| Some((*p, from_start.cost(*p)?))));
+            }
+            if self.chunk_of(node.coord) == goal_chunk
+                && let Some(c) = to_goal.cost(node.coord)
+            {
+                successors.push((goal, c));
+            }
+            for (next, edge_cost) in successors {
+                let next_cost = current_cost.saturating_add(edge_cost);
+                if costs.get(&next).is_none_or(|c| *c > next_cost) {
+                    costs.insert(next, next_cost);
+                    came_from.insert(next, node.coord);
+                    open.push(Node {
+                        coord: next,
+                        score: next_cost.saturating_add(next.unsigned_distance_to(goal)),
+                    });
+                }
+            }
+        }
+        if !came_from.contains_key(&goal) {
+            return None;
+        }
+        let waypoints = reconstruct_path(&came_from, goal);
+        self.refine(&waypoints, &cost)
+    }
+
+    /// Converts an abstract path into a coordinate path
+    fn refine(&self, waypoints: &[Hex], cost: impl Fn(Hex, Hex) -> Option<u32>) -> Option<HexPath> {
+        let mut path = HexPath {
+            coords: vec![*waypoints.first()?],
+            cost: 0,
+        };
+        for pair in waypoints.windows(2) {
+            let [from, to] = [pair[0], pair[1]];
+            let chunk = self.chunk_of(from);
+            if self.chunk_of(to) == chunk {
+                let section =
+                    AStar::new(from, to, Self::chunk_cost(self.chunk_radius, chunk, &cost))
+                        .find()?;
+                path.coords.extend(section.coords.into_iter().skip(1));
+                path.cost = path.cost.saturating_add(section.cost);
+            } else {
+                path.coords.push(to);
+                path.cost = path.cost.saturating_add(cost(from, to)?);
+            }
+        }
+        Some(path)
+    }
+
+    /// Restricts `cost` to the coordinates of `chunk`
+    fn chunk_cost(
+        radius: u32,
+        chunk: Hex,
+        cost: impl Fn(Hex, Hex) -> Option<u32>,
+    ) -> impl Fn(Hex, Hex) -> Option<u32> {
+        move |a, b| {
+            (b.to_lower_res(radius) == chunk)
+                .then(|| cost(a, b))
+                .flatten()
+        }
+    }
+
+    /// Retrieves or computes the abstract graph of `chunk`
+    fn chunk_graph(&mut self, chunk: Hex, cost: impl Fn(Hex, Hex) -> Option<u32>) -> &ChunkGraph {
+        if !self.chunks.contains_key(&chunk) {
+            let graph = self.compute_chunk_graph(chunk, &cost);
+            self.chunks.insert(chunk, graph);
+        }
+        &self.chunks[&chunk]
+    }
+
+    fn compute_chunk_graph(
+        &self,
+        chunk: Hex,
+        cost: impl Fn(Hex, Hex) -> Option<u32>,
+    ) -> ChunkGraph {
+        let portals: Vec<_> = chunk
+            .all_neighbors()
+            .into_iter()
+            .flat_map(|neighbor| self.portals_between(chunk, neighbor, &cost))
+            .collect();
+        let mut graph = ChunkGraph::default();
+        for &(portal, _) in &portals {
+            if graph.edges.contains_key(&portal) {
+                continue;
+            }
+            let map = dijkstra_map(
+                [portal],
+                u32::MAX,
+                Self::chunk_cost(self.chunk_radius, chunk, &cost),
+            );
+            let mut edges: Vec<_> = portals
+                .iter()
+                .filter(|(p, _)| *p != portal)
+                .filter_map(|(p, _)| Some((*p, map.cost(*p)?)))
+                .collect();
+            edges.sort_unstable_by_key(|(p, _)| (p.x, p.y));
+            edges.dedup();
+            edges.extend(
+                portals
+                    .iter()
+                    .filter(|(p, _)| *p == portal)
+                    .filter_map(|(p, outside)| Some((*outside, cost(*p, *outside)?))),
+            );
+            graph.edges.insert(portal, edges);
+        }
+        graph
+    }
+
+    /// Computes the portals between `chunk` and its `neighbor` chunk, as
+    /// coordinate pairs (inside `chunk`, inside `neighbor`).
+    ///
+    /// The result is consistent when swapping both chunks
+    fn portals_between(
+        &self,
+        chunk: Hex,
+        neighbor: Hex,
+        cost: impl Fn(Hex, Hex) -> Option<u32>,
+    ) -> Vec<(Hex, Hex)> {
+        // Canonical ordering
+        if (neighbor.x, neighbor.y) < (chunk.x, chunk.y) {
+            return self
+                .portals_between(neighbor, chunk, cost)
+                .into_iter()
+                .map(|(a, b)| (b, a))
+                .collect();
+        }
+        let radius = self.chunk_radius;
+        // Passable border crossings
+        let crossings: Vec<_> = chunk
+            .to_higher_res(radius)
+            .ring(radius)
+            .flat_map(|a| a.all_neighbors().map(move |b| (a, b)))
+            .filter(|(_, b)| b.to_lower_res(radius) == neighbor)
+            .filter(|(a, b)| cost(*a, *b).is_some() && cost(*b, *a).is_some())
+            .collect();
+        // Groups contiguous crossings in entrances
+        let touches = |(a, b): (Hex, Hex), (c, d): (Hex, Hex)| {
+            a.unsigned_distance_to(c) <= 1 && b.unsigned_distance_to(d) <= 1
+        };
+        let mut entrances: Vec<Vec<(Hex, Hex)>> = Vec::new();
+        for crossing in crossings {
+            let (touching, mut others): (Vec<_>, Vec<_>) = entrances
+                .into_iter()
+                .partition(|e| e.iter().any(|c| touches(*c, crossing)));
+            let mut merged: Vec<_> = touching.into_iter().flatten().collect();
+            merged.push(crossing);
+            others.push(merged);
+            entrances = others;
+        }
+        // Selects the most central crossing of every entrance
+        entrances
+            .into_iter()
+            .filter_map(|entrance| {
+                entrance.iter().copied().min_by_key(|(a, _)| {
+                    entrance
+                        .iter()
+                        .map(|(c, _)| a.unsigned_distance_to(*c))
+                        .sum::<u32>()
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::algorithms::a_star;
+
+    fn cost(a: Hex, b: Hex) -> Option<u32> {
+        let wall = b.ulength() > 30
+            || (b.x * 5 + b.y * 11 + b.x * b.y).rem_euclid(7) == 0
+            || (b.x == 10 && b.y != 4);
+        (!wall).then_some(1 + u32::from(a.y > 0))
+    }
+
+    fn check_path(path: &HexPath, start: Hex, goal: Hex) {
+        assert_eq!(path.start(), Some(start));
+        assert_eq!(path.end(), Some(goal));
+        let total: u32 = path
+            .coords
+            .windows(2)
+            .map(|p| {
+                assert_eq!(p[0].unsigned_distance_to(p[1]), 1);
+                cost(p[0], p[1]).unwrap()
+            })
+            .sum();
+        assert_eq!(total, path.cost);
+    }
+
+    #[test]
+    fn matches_a_star() {
+        let mut pathfinder = HierarchicalPathfinder::new(4);
+        let start = Hex::new(-1, 0);
+        for goal in [
+            Hex::new(20, -5),
+            Hex::new(-25, 10),
+            Hex::new(0, 2),
+            Hex::new(12, 12),
+        ] {
+            let expected = AStar::new(start, goal, cost).find();
+            let path = pathfinder.find(start, goal, cost);
+            assert_eq!(path.is_some(), expected.is_some());
+            let Some(path) = path else {
+                continue;
+            };
+            check_path(&path, start, goal);
+            let expected = expected.unwrap();
+            assert!(path.cost >= expected.cost);
+            // Near optimal
+            assert!(path.cost <= expected.cost * 3 / 2);
+        }
+        // Unreachable
+        assert!(pathfinder.find(start, Hex::new(40, 0), cost).is_none());
+    }
+
+    #[test]
+    fn bounded_search() {
+        let goal = Hex::new(25, -5);
+        let walled = |_, b: Hex| (b.unsigned_distance_to(goal) != 1).then_some(1);
+        let mut pathfinder = HierarchicalPathfinder::new(4).with_max_expanded_nodes(500);
+        assert!(pathfinder.find(Hex::ZERO, goal, walled).is_none());
+        // The bound doesn't prevent reachable goals
+        pathfinder.clear();
+        let path = pathfinder.find(Hex::ZERO, goal, |_, _| Some(1)).unwrap();
+        assert_eq!(path.end(), Some(goal));
+    }
+
+    #[test]
+    fn invalidation() {
+        let mut pathfinder = HierarchicalPathfinder::new(3);
+        let start = Hex::new(1, 0);
+        let goal = Hex::new(20, 0);
+        let path = pathfinder.find(start, goal, cost).unwrap();
+        assert!(path.coords.contains(&Hex::new(10, 4)));
+        assert!(pathfinder.is_cached(pathfinder.chunk_of(Hex::new(10, 4))));
+
+        let closed = |a, b: Hex| (b != Hex::new(10, 4)).then(|| cost(a, b)).flatten();
+        pathfinder.invalidate(Hex::new(10, 4));
+        assert!(!pathfinder.is_cached(pathfinder.chunk_of(Hex::new(10, 4))));
+        assert!(pathfinder.find(start, goal, closed).is_none());
+        assert!(a_star(start, goal, closed).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{Hex, hex::SEGMENT_EPSILON};
+use glam::Vec2;
+
+/// How [`line_of_sight`] handles rays grazing blocking coordinates, when they
+/// pass exactly through a vertex or along an edge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VertexPolicy {
+    /// The ray is blocked only if the coordinates on *both* sides of the
+    /// grazed vertex or edge are blocking
+    #[default]
+    Permissive,
+    /// The ray is blocked if the coordinates on *either* side of the grazed
+    /// vertex or edge is blocking
+    Strict,
+}
+
+/// Result of a [`line_of_sight`] check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineOfSight {
+    /// Whether the target is visible
+    pub visible: bool,
+    /// The first blocking coordinate obstructing the view, if any
+    pub blocker: Option<Hex>,
+}
+
+/// Checks if `b` is visible from `a`, using the exact segment between both
+/// coordinate centers.
+///
+/// This algorithm takes in account coordinates *visibility* through the
+/// `blocking` argument. (*Blocking* coordinates should return `true`).
+/// `a` and `b` are never considered blocking, so blocking targets can be
+/// visible.
+///
+/// Unlike [`Hex::line_to`], which breaks ties arbitrarily, every coordinate
+/// touched by the segment is checked (see [`Hex::supercover_line_to`]):
+/// * Coordinates crossed by the segment always block the view
+/// * Coordinates only grazed by the segment, on a vertex or along an edge,
+///   block the view according to the given `policy`
+///
+/// The result is symmetric: `line_of_sight(a, b, ..)` and
+/// `line_of_sight(b, a, ..)` have the same visibility.
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::{VertexPolicy, line_of_sight};
+///
+/// // The segment runs along the edge between `hex(0, 1)` and `hex(1, 0)`
+/// let target = hex(1, 1);
+/// let blocking = |h: Hex| h == hex(1, 0);
+/// let permissive = line_of_sight(Hex::ZERO, target, VertexPolicy::Permissive, blocking);
+/// assert!(permissive.visible);
+/// let strict = line_of_sight(Hex::ZERO, target, VertexPolicy::Strict, blocking);
+/// assert!(!strict.visible);
+/// assert_eq!(strict.blocker, Some(hex(1, 0)));
+/// ```
+pub fn line_of_sight(
+    a: Hex,
+    b: Hex,
+    policy: VertexPolicy,
+    blocking: impl Fn(Hex) -> bool,
+) -> LineOfSight {
+    let dir = (b - a).as_vec2();
+    // Grazed blocking coordinates, with their side of the segment and their
+    // contact range
+    let mut grazed: Vec<(Hex, bool, [f32; 2])> = Vec::new();
+    for coord in a.supercover_line_to(b) {
+        if coord == a || coord == b || !blocking(coord) {
+            continue;
+        }
+        let local = coord - a;
+        let crossed = local
+            .segment_overlap(Vec2::ZERO, dir, -SEGMENT_EPSILON)
+            .is_some_and(|[t_in, t_out]| t_out > t_in);
+        let blocked = if crossed || policy == VertexPolicy::Strict {
+            true
+        } else {
+            let side = dir.perp_dot(local.as_vec2()) > 0.0;
+            let Some(range) = local.segment_overlap(Vec2::ZERO, dir, SEGMENT_EPSILON) else {
+                continue;
+            };
+            let squeezed = grazed.iter().any(|(_, other_side, other_range)| {
+                *other_side != side
+                    && range[0] <= other_range[1] + SEGMENT_EPSILON
+                    && other_range[0] <= range[1] + SEGMENT_EPSILON
+            });
+            grazed.push((coord, side, range));
+            squeezed
+        };
+        if blocked {
+            return LineOfSight {
+                visible: false,
+                blocker: Some(coord),
+            };
+        }
+    }
+    LineOfSight {
+        visible: true,
+        blocker: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symmetry() {
+        let blocking = |h: Hex| (h.x * 3 + h.y * 5 + h.x * h.y).rem_euclid(4) == 0;
+        for policy in [VertexPolicy::Permissive, VertexPolicy::Strict] {
+            for a in Hex::ZERO.range(4) {
+                for b in Hex::ZERO.range(4) {
+                    assert_eq!(
+                        line_of_sight(a, b, policy, blocking).visible,
+                        line_of_sight(b, a, policy, blocking).visible,
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edge_grazing() {
+        let target = Hex::new(2, 2);
+        let test = |blockers: &[Hex], policy| {
+            line_of_sight(Hex::ZERO, target, policy, |h| blockers.contains(&h))
+        };
+        for policy in [VertexPolicy::Permissive, VertexPolicy::Strict] {
+            assert!(test(&[], policy).visible);
+            // Crossed coordinate
+            let res = test(&[Hex::new(1, 1)], policy);
+            assert!(!res.visible);
+            assert_eq!(res.blocker, Some(Hex::new(1, 1)));
+            // Both sides
+            let res = test(&[Hex::new(1, 2), Hex::new(2, 1)], policy);
+            assert!(!res.visible);
+            // Out of the segment
+            assert!(test(&[Hex::new(2, 0)], policy).visible);
+        }
+        // Single side
+        let blockers = [Hex::new(0, 1), Hex::new(2, 1)];
+        assert!(test(&blockers, VertexPolicy::Permissive).visible);
+        let res = test(&blockers, VertexPolicy::Strict);
+        assert!(!res.visible);
+        assert_eq!(res.blocker, Some(Hex::new(0, 1)));
+    }
+
+    #[test]
+    fn vertex_grazing() {
+        // The segment passes through the vertex shared by `hex(1, 0)`,
+        // `hex(2, 0)` and `hex(2, -1)`, going from `hex(1, 0)` to `hex(2, 0)`
+        let target = Hex::new(5, -1);
+        let blocking = |h| h == Hex::new(2, -1);
+        assert!(line_of_sight(Hex::ZERO, target, VertexPolicy::Permissive, blocking).visible);
+        let res = line_of_sight(Hex::ZERO, target, VertexPolicy::Strict, blocking);
+        assert!(!res.visible);
+        assert_eq!(res.blocker, Some(Hex::new(2, -1)));
+        let blocking = |h| h == Hex::new(2, 0);
+        assert!(!line_of_sight(Hex::ZERO, target, VertexPolicy::Permissive, blocking).visible);
+    }
+}
@@ -1,7 +1,48 @@
+//! Non exhaustive collection of classic algorithms.
+//!
+//! ## Wrapping maps
+//!
+//! Most algorithms work on an unbounded hexagonal plane. The following entry
+//! points take a [`HexTopology`](crate::HexTopology), allowing to cross the
+//! seams of wrapping maps:
+//!
+//! * [`a_star_in`] and [`AStar::with_topology`]
+//! * [`dijkstra_map_in`]
+//! * [`field_of_movement_in`], [`movement_field_in`] and
+//!   [`edge_movement_field_in`]
+//! * [`shadowcast_fov_in`]
+//!
+//! [`flow_field`] wraps through the storage instead (See
+//! [`HexStore::wrapped_coord`](crate::storage::HexStore::wrapped_coord)).
+//!
+//! [`range_fov`], [`directional_fov`], [`cone_fov`], [`line_of_sight`] and
+//! [`HierarchicalPathfinder`] ignore wrapping.
+mod dijkstra;
 mod field_of_movement;
+mod flood_fill;
+mod flow_field;
 mod fov;
+mod goal;
+mod hierarchical;
+mod line_of_sight;
 mod pathfinding;
 
-pub use field_of_movement::field_of_movement;
-pub use fov::{directional_fov, range_fov};
-pub use pathfinding::a_star;
+pub use dijkstra::{DijkstraMap, dijkstra_map, dijkstra_map_in};
+pub use field_of_movement::{
+    MovementField, edge_movement_field, edge_movement_field_in, field_of_movement,
+    field_of_movement_in, field_of_movement_into, movement_field, movement_field_in,
+};
+pub use flood_fill::{
+    Components, connected_components, flood_fill, store_connected_components, store_flood_fill,
+};
+#[cfg(feature = "grid")]
+pub use flood_fill::{connected_components_with_walls, flood_fill_with_walls};
+pub use flow_field::flow_field;
+pub use fov::{
+    cone_fov, cone_fov_partial, directional_fov, range_fov, range_fov_into, shadowcast_fov,
+    shadowcast_fov_in, shadowcast_fov_into, shadowcast_fov_partial,
+};
+pub use goal::{GoalPredicate, PathGoal};
+pub use hierarchical::HierarchicalPathfinder;
+pub use line_of_sight::{LineOfSight, VertexPolicy, line_of_sight};
+pub use pathfinding::{AStar, HexPath, a_star, a_star_in};
@@ -1,14 +1,15 @@
-use crate::Hex;
+use super::PathGoal;
+use crate::{Hex, HexTopology, Unbounded};
 #[cfg(feature = "bevy_platform")]
 use bevy_platform::collections::HashMap;
 use std::collections::BinaryHeap;
 #[cfg(not(feature = "bevy_platform"))]
 use std::collections::HashMap;
 
-struct Node {
-    coord: Hex,
+pub(super) struct Node {
+    pub coord: Hex,
     /// cost + heuristic
-    score: u32,
+    pub score: u32,
 }
 
 impl PartialEq for Node {
@@ -31,7 +32,7 @@ impl Ord for Node {
     }
 }
 
-fn reconstruct_path(came_from: &HashMap<Hex, Hex>, end: Hex) -> Vec<Hex> {
+pub(super) fn reconstruct_path(came_from: &HashMap<Hex, Hex>, end: Hex) -> Vec<Hex> {
     let mut path: Vec<_> =
         std::iter::successors(Some(end), move |&current| came_from.get(&current).copied())
             .collect();
@@ -46,6 +47,9 @@ fn reconstruct_path(came_from: &HashMap<Hex, Hex>, end: Hex) -> Vec<Hex> {
 /// This function already takes care of heuristics based on the distance between
 /// `start` and `end`.
 ///
+/// > See [`AStar`] to retrieve the path cost, customize the heuristic or bound
+/// > the search
+///
 /// # Arguments
 ///
 /// * `start` - start node
@@ -108,39 +112,448 @@ fn reconstruct_path(came_from: &HashMap<Hex, Hex>, end: Hex) -> Vec<Hex> {
 /// });
 /// ```
 pub fn a_star(start: Hex, end: Hex, cost: impl Fn(Hex, Hex) -> Option<u32>) -> Option<Vec<Hex>> {
-    let heuristic = |h: Hex| h.unsigned_distance_to(end);
-
-    // We return early if the end is not included
-    cost(end, end)?;
-    let start_node = Node {
-        coord: start,
-        score: heuristic(start) + cost(start, start)?,
-    };
-    let mut open = BinaryHeap::new();
-    open.push(start_node);
-    let mut costs = HashMap::new();
-    costs.insert(start, 0);
-    let mut came_from = HashMap::new();
-
-    while let Some(node) = open.pop() {
-        if node.coord == end {
-            return Some(reconstruct_path(&came_from, end));
+    AStar::new(start, end, cost).find().map(|path| path.coords)
+}
+
+/// Performs A star pathfinding between `start` and `end` in the given
+/// `topology`, allowing paths to cross the seams of wrapping maps.
+///
+/// `start` and `end` should be canonical coordinates
+/// (See [`HexTopology::canonical`]).
+///
+/// > See [`a_star`] and [`AStar::with_topology`]
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::a_star_in;
+///
+/// let bounds = HexBounds::new(Hex::ZERO, 5);
+/// let path = a_star_in(bounds, hex(5, 0), hex(-5, 5), |_, _| Some(1)).unwrap();
+/// assert_eq!(path, vec![hex(5, 0), hex(-5, 5)]);
+/// ```
+pub fn a_star_in(
+    topology: impl HexTopology,
+    start: Hex,
+    end: Hex,
+    cost: impl Fn(Hex, Hex) -> Option<u32>,
+) -> Option<Vec<Hex>> {
+    AStar::new(start, end, cost)
+        .with_topology(topology)
+        .find()
+        .map(|path| path.coords)
+}
+
+/// Path computed by [`AStar::find`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexPath {
+    /// Path coordinates, from start to destination (both included)
+    pub coords: Vec<Hex>,
+    /// Total cost of the path
+    pub cost: u32,
+}
+
+impl HexPath {
+    /// Returns the first coordinate of the path
+    #[inline]
+    #[must_use]
+    pub fn start(&self) -> Option<Hex> {
+        self.coords.first().copied()
+    }
+
+    /// Returns the last coordinate of the path, which is the reached
+    /// destination.
+    ///
+    /// For searches with multiple destinations (See [`PathGoal`]) this is the
+    /// goal which was reached
+    #[inline]
+    #[must_use]
+    pub fn end(&self) -> Option<Hex> {
+        self.coords.last().copied()
+    }
+}
+
+/// Configurable A star pathfinding between a start coordinate and a
+/// [`PathGoal`].
+///
+/// Compared to [`a_star`], this builder allows to:
+/// * Search a path to any of multiple destinations, like a set of coordinates,
+///   [`HexBounds`](crate::HexBounds) or a
+///   [`GoalPredicate`](super::GoalPredicate)
+/// * Retrieve the total cost of the path through [`HexPath::cost`]
+/// * Customize or weight the heuristic, for example when the minimum move cost
+///   is greater than 1 or for *weighted A star*
+/// * Bound the search with a maximum path cost or a maximum amount of expanded
+///   nodes, which is useful on large open maps
+/// * Search on wrapping maps, through a [`HexTopology`]
+///
+/// The `cost` function follows the same convention as [`a_star`].
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::AStar;
+///
+/// let start = hex(0, 0);
+/// let end = hex(10, 0);
+/// // Every move costs 2
+/// let path = AStar::new(start, end, |_, _| Some(2))
+///     // The heuristic is scaled by the minimum move cost to stay accurate
+///     .with_heuristic_weight(2.0)
+///     .with_max_cost(100)
+///     .with_max_expanded_nodes(1_000)
+///     .find()
+///     .unwrap();
+/// assert_eq!(path.cost, 20);
+/// assert_eq!(path.coords.len(), 11);
+/// ```
+///
+/// - Find the path to the closest of multiple goals
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::{AStar, GoalPredicate};
+///
+/// let towns = vec![hex(10, 0), hex(-3, 2), hex(5, 5)];
+/// let path = AStar::new(Hex::ZERO, &towns, |_, _| Some(1))
+///     .find()
+///     .unwrap();
+/// assert_eq!(path.end(), Some(hex(-3, 2)));
+/// // Any coordinate adjacent to an enemy
+/// let enemy = hex(4, -8);
+/// let goal = GoalPredicate(|h: Hex| h.unsigned_distance_to(enemy) == 1);
+/// let path = AStar::new(Hex::ZERO, goal, |_, _| Some(1))
+///     // Predicate goals must be bounded, in case no coordinate matches
+///     .with_max_cost(50)
+///     .find()
+///     .unwrap();
+/// assert_eq!(path.cost, 7);
+/// ```
+///
+/// - Find a path crossing the seam of a wrapping map
+///
+/// ```rust
+/// # use hexx::*;
+/// use hexx::algorithms::AStar;
+///
+/// let bounds = HexBounds::new(Hex::ZERO, 10);
+/// let path = AStar::new(hex(10, 0), hex(-10, 10), |_, _| Some(1))
+///     .with_topology(bounds)
+///     .find()
+///     .unwrap();
+/// assert_eq!(path.cost, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AStar<C, G = Hex, H = fn(Hex) -> u32, T = Unbounded> {
+    /// Start coordinate
+    pub start: Hex,
+    /// Destination, either a single coordinate or a [`PathGoal`]
+    pub goal: G,
+    /// Cost function taking a node pair (`a` -> `b`) and returning the
+    /// logical cost to go from `a` to `b`
+    pub cost: C,
+    /// Optional custom heuristic, estimating the remaining cost from a
+    /// coordinate to `goal`. If `None` [`PathGoal::topology_estimate`] is used
+    pub heuristic: Option<H>,
+    /// Factor applied to the heuristic.
+    ///
+    /// Should be the minimum move cost to keep the default heuristic
+    /// admissible, greater values trade path optimality for speed
+    pub heuristic_weight: f32,
+    /// Optional maximum total path cost, paths above this cost are discarded
+    pub max_cost: Option<u32>,
+    /// Optional maximum amount of nodes to expand before giving up
+    pub max_expanded_nodes: Option<usize>,
+    /// World topology, defining neighbors and distances
+    pub topology: T,
+}
+
+impl<C, G> AStar<C, G>
+where
+    C: Fn(Hex, Hex) -> Option<u32>,
+    G: PathGoal,
+{
+    /// Setup a new A star search between `start` and `goal`
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - start node
+    /// * `goal` - destination node or [`PathGoal`]
+    /// * `cost` - cost function taking a node pair (`a` -> `b`) and returning
+    ///   the logical cost to go from `a` to `b`
+    #[must_use]
+    pub const fn new(start: Hex, goal: G, cost: C) -> Self {
+        Self {
+            start,
+            goal,
+            cost,
+            heuristic: None,
+            heuristic_weight: 1.0,
+            max_cost: None,
+            max_expanded_nodes: None,
+            topology: Unbounded,
         }
-        let current_cost = costs[&node.coord];
-        for neighbor in node.coord.all_neighbors() {
-            let Some(cost) = cost(node.coord, neighbor) else {
+    }
+}
+
+impl<C, G, H, T> AStar<C, G, H, T> {
+    /// Specify a custom heuristic, estimating the remaining cost from a given
+    /// coordinate to the destination.
+    ///
+    /// The heuristic is still scaled by [`Self::heuristic_weight`]
+    #[must_use]
+    pub fn with_heuristic<H2>(self, heuristic: H2) -> AStar<C, G, H2, T>
+    where
+        H2: Fn(Hex) -> u32,
+    {
+        AStar {
+            start: self.start,
+            goal: self.goal,
+            cost: self.cost,
+            heuristic: Some(heuristic),
+            heuristic_weight: self.heuristic_weight,
+            max_cost: self.max_cost,
+            max_expanded_nodes: self.max_expanded_nodes,
+            topology: self.topology,
+        }
+    }
+
+    /// Specify the world topology, allowing paths to cross the seams of
+    /// wrapping maps.
+    ///
+    /// Neighbors are resolved through [`HexTopology::neighbors`], so `start`
+    /// and the goal should be canonical coordinates
+    /// (See [`HexTopology::canonical`])
+    #[must_use]
+    pub fn with_topology<T2>(self, topology: T2) -> AStar<C, G, H, T2>
+    where
+        T2: HexTopology,
+    {
+        AStar {
+            start: self.start,
+            goal: self.goal,
+            cost: self.cost,
+            heuristic: self.heuristic,
+            heuristic_weight: self.heuristic_weight,
+            max_cost: self.max_cost,
+            max_expanded_nodes: self.max_expanded_nodes,
+            topology,
+        }
+    }
+
+    /// Specify a factor for the heuristic.
+    ///
+    /// * Use the minimum move cost to keep the heuristic admissible
+    /// * Use greater values for a faster but potentially sub-optimal *weighted
+    ///   A star*
+    #[must_use]
+    #[inline]
+    pub const fn with_heuristic_weight(mut self, weight: f32) -> Self {
+        self.heuristic_weight = weight;
+        self
+    }
+
+    /// Specify a maximum total path cost. Coordinates which can't be reached
+    /// under this cost are ignored
+    #[must_use]
+    #[inline]
+    pub const fn with_max_cost(mut self, max_cost: u32) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Specify a maximum amount of nodes to expand. The search gives up and
+    /// returns `None` when this limit is reached
+    #[must_use]
+    #[inline]
+    pub const fn with_max_expanded_nodes(mut self, max_expanded_nodes: usize) -> Self {
+        self.max_expanded_nodes = Some(max_expanded_nodes);
+        self
+    }
+}
+
+impl<C, G, H, T> AStar<C, G, H, T>
+where
+    C: Fn(Hex, Hex) -> Option<u32>,
+    G: PathGoal,
+    H: Fn(Hex) -> u32,
+    T: HexTopology,
+{
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn heuristic(&self, coord: Hex) -> u32 {
+        let estimate = self.heuristic.as_ref().map_or_else(
+            || self.goal.topology_estimate(coord, &self.topology),
+            |h| h(coord),
+        );
+        (estimate as f32 * self.heuristic_weight) as u32
+    }
+
+    /// Performs the search, returning the path and its total cost or `None` if
+    /// no path was found within the limits
+    #[must_use]
+    pub fn find(self) -> Option<HexPath> {
+        // We return early if the start or goal are not included
+        if self.goal.is_excluded(|h| (self.cost)(h, h).is_some()) {
+            return None;
+        }
+        (self.cost)(self.start, self.start)?;
+        let mut open = BinaryHeap::new();
+        open.push(Node {
+            coord: self.start,
+            score: self.heuristic(self.start),
+        });
+        let mut costs = HashMap::new();
+        costs.insert(self.start, 0_u32);
+        let mut came_from = HashMap::new();
+        let mut expanded = 0;
+
+        while let Some(node) = open.pop() {
+            let current_cost = costs[&node.coord];
+            // Skip outdated entries
+            if node.score > current_cost.saturating_add(self.heuristic(node.coord)) {
                 continue;
-            };
-            let neighbor_cost = current_cost + cost;
-            if !costs.contains_key(&neighbor) || costs[&neighbor] > neighbor_cost {
-                came_from.insert(neighbor, node.coord);
-                costs.insert(neighbor, neighbor_cost);
-                open.push(Node {
-                    coord: neighbor,
-                    score: neighbor_cost + heuristic(neighbor),
+            }
+            if self.goal.is_goal(node.coord) {
+                return Some(HexPath {
+                    coords: reconstruct_path(&came_from, node.coord),
+                    cost: current_cost,
                 });
             }
+            if self.max_expanded_nodes.is_some_and(|max| expanded >= max) {
+                return None;
+            }
+            expanded += 1;
+            for neighbor in self.topology.neighbors(node.coord).into_iter().flatten() {
+                let Some(cost) = (self.cost)(node.coord, neighbor) else {
+                    continue;
+                };
+                let neighbor_cost = current_cost.saturating_add(cost);
+                if self.max_cost.is_some_and(|max| neighbor_cost > max) {
+                    continue;
+                }
+                if costs.get(&neighbor).is_none_or(|c| *c > neighbor_cost) {
+                    came_from.insert(neighbor, node.coord);
+                    costs.insert(neighbor, neighbor_cost);
+                    open.push(Node {
+                        coord: neighbor,
+                        score: neighbor_cost.saturating_add(self.heuristic(neighbor)),
+                    });
+                }
+            }
         }
+        None
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_cost() {
+        let end = Hex::new(5, -3);
+        let path = AStar::new(Hex::ZERO, end, |_, _| Some(3))
+            .with_heuristic_weight(3.0)
+            .find()
+            .unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.start(), Some(Hex::ZERO));
+        assert_eq!(path.end(), Some(end));
+        assert_eq!(
+            a_star(Hex::ZERO, end, |_, _| Some(3)).unwrap().len(),
+            path.coords.len()
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let end = Hex::new(10, 0);
+        let search = AStar::new(Hex::ZERO, end, |_, _| Some(1));
+        assert!(search.clone().with_max_cost(9).find().is_none());
+        assert!(search.clone().with_max_cost(10).find().is_some());
+        assert!(search.with_max_expanded_nodes(5).find().is_none());
+        // Unreachable destination with an unbounded search would never end
+        let path = AStar::new(Hex::ZERO, end, |_, b| {
+            (b.unsigned_distance_to(end) != 1).then_some(1)
+        })
+        .with_max_expanded_nodes(1_000)
+        .find();
+        assert!(path.is_none());
+    }
+
+    #[test]
+    fn custom_heuristic() {
+        let end = Hex::new(-4, 7);
+        let path = AStar::new(Hex::ZERO, end, |_, _| Some(1))
+            .with_heuristic(|_| 0)
+            .find()
+            .unwrap();
+        assert_eq!(path.cost, Hex::ZERO.unsigned_distance_to(end));
+    }
+
+    #[test]
+    fn multiple_goals() {
+        let goals = [Hex::new(8, 0), Hex::new(-2, -2), Hex::new(0, 6)];
+        let path = AStar::new(Hex::ZERO, goals, |_, _| Some(1)).find().unwrap();
+        assert_eq!(path.end(), Some(Hex::new(-2, -2)));
+        assert_eq!(path.cost, 4);
+        // Excluded goals are ignored
+        let path = AStar::new(Hex::ZERO, &goals, |_, b| {
+            (b != Hex::new(-2, -2)).then_some(1)
+        })
+        .find()
+        .unwrap();
+        assert_eq!(path.end(), Some(Hex::new(0, 6)));
+        // Fully excluded goals
+        let path = AStar::new(Hex::ZERO, goals.as_slice(), |_, b| {
+            (!goals.contains(&b)).then_some(1)
+        })
+        .find();
+        assert!(path.is_none());
+    }
+
+    #[test]
+    fn bounds_goal() {
+        let bounds = crate::HexBounds::new(Hex::new(10, -10), 3);
+        let path = AStar::new(Hex::ZERO, bounds, |_, _| Some(1))
+            .find()
+            .unwrap();
+        assert!(bounds.is_in_bounds(path.end().unwrap()));
+        assert_eq!(path.cost, 10 - 3);
+        // Coordinates inside the bounds are goals
+        let path = AStar::new(Hex::new(10, -10), bounds, |_, _| Some(1))
+            .find()
+            .unwrap();
+        assert_eq!(path.cost, 0);
+    }
+
+    #[test]
+    fn wrapping_topology() {
+        use crate::{
+            HexOrientation,
+            storage::{RectMetadata, WrapStrategy},
+        };
+
+        let meta = RectMetadata::from_half_size([8, 4])
+            .with_orientation(HexOrientation::Pointy)
+            .with_wrap_strategies([WrapStrategy::Cycle, WrapStrategy::Clamp]);
+        let [start, end] = [Hex::new(-8, 0), Hex::new(6, 0)];
+        let path = AStar::new(start, end, |_, _| Some(1))
+            .with_topology(&meta)
+            .find()
+            .unwrap();
+        assert_eq!(path.cost, meta.distance(start, end));
+        assert_eq!(path.cost, 2);
+        assert!(path.coords.iter().all(|h| meta.contains_hex(*h)));
+        // Without wrapping
+        let path = AStar::new(start, end, |_, _| Some(1)).find().unwrap();
+        assert_eq!(path.cost, 14);
     }
-    None
 }
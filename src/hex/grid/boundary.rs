@@ -0,0 +1,120 @@
+use super::{GridEdge, GridVertex};
+use crate::{Hex, HexLayout};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashSet;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashSet;
+
+/// Closed loop of [`GridEdge`] outlining a region of coordinates.
+///
+/// Every edge has its `origin` inside the region and points towards a
+/// coordinate outside of it. The edges are ordered so that the last vertex of
+/// an edge is the first vertex of the next one (See [`GridEdge::vertices`]),
+/// the last edge connecting back to the first.
+///
+/// Outer boundaries follow the
+/// [`EdgeDirection::clockwise`](crate::EdgeDirection::clockwise) order around
+/// the region, holes go the opposite way around the empty area so the region is
+/// always on the same side of the loop.
+///
+/// > See [`HexIterExt::boundary_loops`](crate::HexIterExt::boundary_loops)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct BoundaryLoop {
+    /// The ordered edges of the loop
+    pub edges: Vec<GridEdge>,
+    /// Whether the loop is the boundary of a hole in the region
+    pub is_hole: bool,
+}
+
+impl BoundaryLoop {
+    /// Computes all boundary loops of the region made of `coords`.
+    ///
+    /// Outer boundaries come first, in the order of their first coordinate in
+    /// `coords`, followed by the holes.
+    #[must_use]
+    pub fn from_region(coords: impl IntoIterator<Item = Hex>) -> Vec<Self> {
+        let mut region = Vec::new();
+        let mut set = HashSet::new();
+        for coord in coords {
+            if set.insert(coord) {
+                region.push(coord);
+            }
+        }
+        let mut visited = HashSet::new();
+        let mut loops = Vec::new();
+        for edge in region.iter().flat_map(|h| h.all_edges()) {
+            if set.contains(&edge.destination()) || visited.contains(&edge) {
+                continue;
+            }
+            let edges = Self::trace(edge, &set);
+            visited.extend(edges.iter().copied());
+            loops.push(Self {
+                is_hole: Self::signed_area(&edges) < 0.0,
+                edges,
+            });
+        }
+        loops.sort_by_key(|l| l.is_hole);
+        loops
+    }
+
+    /// Follows the boundary of `region` from `start`, until reaching it again
+    fn trace(start: GridEdge, region: &HashSet<Hex>) -> Vec<GridEdge> {
+        let mut edges = vec![start];
+        let mut current = start;
+        loop {
+            let next_dir = current.direction.clockwise();
+            let next_coord = current.origin + next_dir;
+            // The next edge shares the last vertex of `current`
+            current = if region.contains(&next_coord) {
+                GridEdge {
+                    origin: next_coord,
+                    direction: current.direction.counter_clockwise(),
+                }
+            } else {
+                GridEdge {
+                    origin: current.origin,
+                    direction: next_dir,
+                }
+            };
+            if current == start {
+                return edges;
+            }
+            edges.push(current);
+        }
+    }
+
+    /// Signed area of the loop in a default layout, negative for holes
+    fn signed_area(edges: &[GridEdge]) -> f32 {
+        let layout = HexLayout::default();
+        edges
+            .iter()
+            .map(|e| {
+                let [a, b] = layout.edge_coordinates(*e);
+                a.perp_dot(b)
+            })
+            .sum::<f32>()
+    }
+
+    /// Returns the amount of edges in the loop
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns `true` if the loop has no edges
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns the ordered vertices of the loop, the first vertex of every
+    /// edge
+    #[must_use]
+    pub fn vertices(&self) -> impl ExactSizeIterator<Item = GridVertex> + '_ {
+        self.edges.iter().map(|e| e.vertices()[0])
+    }
+}
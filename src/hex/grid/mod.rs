@@ -1,5 +1,7 @@
+mod boundary;
 mod edge;
 mod vertex;
 
+pub use boundary::BoundaryLoop;
 pub use edge::GridEdge;
 pub use vertex::GridVertex;
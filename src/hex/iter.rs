@@ -44,6 +44,31 @@ pub trait HexIterExt: Iterator {
     /// assert_eq!(bounds.radius, 10);
     /// ```
     fn bounds(self) -> HexBounds;
+
+    /// Method which takes an iterator and computes the ordered boundary loops
+    /// of the region made of all elements, outer boundaries first and then
+    /// holes.
+    ///
+    /// This method will return an empty vector on an empty iterator
+    ///
+    /// > See [`BoundaryLoop::from_region`](crate::BoundaryLoop::from_region)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// // Ring of radius 2, with a hole in the middle
+    /// let loops = Hex::ZERO
+    ///     .range(2)
+    ///     .filter(|h| *h != Hex::ZERO)
+    ///     .boundary_loops();
+    /// assert_eq!(loops.len(), 2);
+    /// assert_eq!(loops[0].len(), 30);
+    /// assert!(loops[1].is_hole);
+    /// assert_eq!(loops[1].len(), 6);
+    /// ```
+    #[cfg(feature = "grid")]
+    fn boundary_loops(self) -> Vec<crate::BoundaryLoop>;
 }
 
 impl<I: Iterator<Item = Hex>> HexIterExt for I {
@@ -66,6 +91,11 @@ impl<I: Iterator<Item = Hex>> HexIterExt for I {
     fn bounds(self) -> HexBounds {
         self.collect()
     }
+
+    #[cfg(feature = "grid")]
+    fn boundary_loops(self) -> Vec<crate::BoundaryLoop> {
+        crate::BoundaryLoop::from_region(self)
+    }
 }
 
 /// Private container for a [`Hex`] [`Iterator`] of known size
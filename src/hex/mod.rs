@@ -26,12 +26,15 @@ pub use iter::HexIterExt;
 use crate::{DirectionWay, EdgeDirection, VertexDirection};
 use glam::{IVec2, IVec3, Vec2};
 #[cfg(feature = "grid")]
-pub use grid::{GridEdge, GridVertex};
+pub use grid::{BoundaryLoop, GridEdge, GridVertex};
 use std::{
     cmp::{max, min},
     fmt::Debug,
 };
 
+/// Tolerance used for segment and hexagon intersections, in axial units
+pub(crate) const SEGMENT_EPSILON: f32 = 1e-4;
+
 /// Hexagonal [axial] coordinates
 ///
 /// # Why Axial ?
@@ -910,6 +913,76 @@ impl Hex {
         }
     }
 
+    #[must_use]
+    /// Computes all coordinates touched by the segment from `self` to `other`,
+    /// ordered by the position where the segment reaches them.
+    ///
+    /// Unlike [`Hex::line_to`], which picks one coordinate per step and breaks
+    /// ties arbitrarily, every coordinate whose hexagon intersects the segment
+    /// is included, even if the segment only grazes one of its vertices or runs
+    /// along one of its edges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let start = Hex::ZERO;
+    /// // The segment runs along the edge between `hex(0, 1)` and `hex(1, 0)`
+    /// let end = Hex::new(1, 1);
+    ///
+    /// let line: Vec<Hex> = start.supercover_line_to(end).collect();
+    /// assert_eq!(line, vec![start, hex(0, 1), hex(1, 0), end]);
+    /// ```
+    pub fn supercover_line_to(self, other: Self) -> impl ExactSizeIterator<Item = Self> {
+        let dir = (other - self).as_vec2();
+        let mut coords: Vec<_> = Self::ZERO
+            .line_to(other - self)
+            .flat_map(|h| std::iter::once(h).chain(h.all_neighbors()))
+            .filter_map(|h| {
+                let [t, _] = h.segment_overlap(Vec2::ZERO, dir, SEGMENT_EPSILON)?;
+                Some((t, h))
+            })
+            .collect();
+        coords.sort_by(|(ta, a), (tb, b)| ta.total_cmp(tb).then(a.x.cmp(&b.x)).then(a.y.cmp(&b.y)));
+        coords.dedup_by_key(|(_, h)| *h);
+        coords.into_iter().map(move |(_, h)| self.const_add(h))
+    }
+
+    #[must_use]
+    /// Computes the parameters `[t_in, t_out]`, in `[0, 1]`, of the portion of
+    /// the segment from `start` to `start + dir` inside the hexagon of `self`.
+    ///
+    /// Positions are expressed in fractional axial coordinates, and the
+    /// hexagon is expanded by `margin` (shrunk if negative)
+    pub(crate) fn segment_overlap(self, start: Vec2, dir: Vec2, margin: f32) -> Option<[f32; 2]> {
+        // Cubic differences between a position in the hexagon and its center
+        // are in `[-1, 1]`
+        const NORMALS: [Vec2; 6] = [
+            Vec2::new(1.0, -1.0),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(-1.0, -2.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(-2.0, -1.0),
+        ];
+        let offset = start - self.as_vec2();
+        let mut range = [0.0_f32, 1.0_f32];
+        for normal in NORMALS {
+            let dist = 1.0 + margin - normal.dot(offset);
+            let speed = normal.dot(dir);
+            if speed.abs() <= f32::EPSILON {
+                if dist < 0.0 {
+                    return None;
+                }
+            } else if speed > 0.0 {
+                range[1] = range[1].min(dist / speed);
+            } else {
+                range[0] = range[0].max(dist / speed);
+            }
+        }
+        (range[0] <= range[1]).then_some(range)
+    }
+
     #[expect(clippy::cast_sign_loss)]
     #[must_use]
     /// Computes all coordinate in a two segment rectiline path from `self` to
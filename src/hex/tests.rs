@@ -607,3 +607,96 @@ fn u64_conversion() {
         }
     }
 }
+
+#[test]
+fn supercover_line_to() {
+    let a = Hex::new(-3, 2);
+    for b in a.range(8) {
+        let line: Vec<_> = a.supercover_line_to(b).collect();
+        assert_eq!(line.first(), Some(&a));
+        assert!(line.contains(&b));
+        // `line_to` coordinates are all included
+        assert!(a.line_to(b).all(|h| line.contains(&h)));
+        // Consecutive coordinates touch each other
+        for pair in line.windows(2) {
+            assert_eq!(pair[0].unsigned_distance_to(pair[1]), 1);
+        }
+        // Brute force check
+        let dir = (b - a).as_vec2();
+        let expected = a
+            .range(9)
+            .filter(|h| (*h - a).segment_overlap(Vec2::ZERO, dir, 1e-4).is_some())
+            .count();
+        assert_eq!(line.len(), expected);
+    }
+    let line: Vec<_> = Hex::ZERO.supercover_line_to(Hex::new(4, 2)).collect();
+    assert_eq!(
+        line,
+        vec![
+            Hex::ZERO,
+            Hex::new(1, 0),
+            Hex::new(1, 1),
+            Hex::new(2, 1),
+            Hex::new(3, 1),
+            Hex::new(3, 2),
+            Hex::new(4, 2),
+        ]
+    );
+}
+
+#[cfg(feature = "grid")]
+mod boundary {
+    use super::*;
+    use crate::{BoundaryLoop, HexIterExt};
+
+    fn assert_closed(boundary: &BoundaryLoop) {
+        let len = boundary.edges.len();
+        for (i, edge) in boundary.edges.iter().enumerate() {
+            let next = boundary.edges[(i + 1) % len];
+            assert!(edge.vertices()[1].equivalent(&next.vertices()[0]));
+        }
+    }
+
+    #[test]
+    fn single_hex() {
+        let loops = std::iter::once(Hex::new(2, -5)).boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert!(!loops[0].is_hole);
+        assert_eq!(loops[0].edges, Hex::new(2, -5).all_edges().to_vec());
+        assert_closed(&loops[0]);
+    }
+
+    #[test]
+    fn holes() {
+        // Two separate regions, the first one with two holes
+        let holes = [Hex::new(1, 0), Hex::new(-2, 1), Hex::new(-2, 2)];
+        let region: Vec<_> = Hex::ZERO
+            .range(4)
+            .filter(|h| !holes.contains(h))
+            .chain(Hex::new(10, 0).range(1))
+            .collect();
+        let loops = region.iter().copied().boundary_loops();
+        assert_eq!(loops.iter().filter(|l| !l.is_hole).count(), 2);
+        assert_eq!(loops.iter().filter(|l| l.is_hole).count(), 2);
+        for boundary in &loops {
+            assert_closed(boundary);
+            for edge in &boundary.edges {
+                assert!(region.contains(&edge.origin));
+                assert!(!region.contains(&edge.destination()));
+            }
+        }
+        // Every boundary edge belongs to a single loop
+        let edge_count = region
+            .iter()
+            .flat_map(|h| h.all_edges())
+            .filter(|e| !region.contains(&e.destination()))
+            .count();
+        assert_eq!(
+            loops.iter().map(BoundaryLoop::len).sum::<usize>(),
+            edge_count
+        );
+        assert_eq!(loops[0].len(), 6 * 9);
+        assert_eq!(loops[1].len(), 6 * 3);
+        assert!(loops[2].is_hole && loops[3].is_hole);
+    }
+}
@@ -231,6 +231,28 @@ impl HexLayout {
         self.__vertex_coordinates(vertex) + origin
     }
 
+    /// Returns the world coordinates of the vertices of a boundary loop, as a
+    /// closed polyline.
+    ///
+    /// The first vertex is not repeated at the end
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let layout = HexLayout::default();
+    /// let loops = Hex::ZERO.range(1).boundary_loops();
+    /// let polyline = layout.boundary_coordinates(&loops[0]);
+    /// assert_eq!(polyline.len(), 18);
+    /// ```
+    #[must_use]
+    pub fn boundary_coordinates(&self, boundary: &crate::BoundaryLoop) -> Vec<Vec2> {
+        boundary
+            .vertices()
+            .map(|v| self.vertex_coordinates(v))
+            .collect()
+    }
+
     fn __vertex_coordinates(&self, vertex: crate::GridVertex) -> Vec2 {
         vertex.direction.world_unit_vector(self)
     }
@@ -38,14 +38,18 @@
 //! * `algorithms`: Enables the [algorithms](crate::algorithms) module with:
 //!   * Field of Movement
 //!   * A Star Pathfinding
+//!   * Dijkstra maps
 //!   * Field of view
 //! * `mesh`: Enables procedural mesh generation
+//! * `mesh_export`: Enables mesh export to Wavefront OBJ, PLY and glTF binary
+//!   formats
 //! * `bevy`: Enables [Bevy](https://bevyengine.org/) support including:
 //!   * `bevy_platform`: Enables [Bevy Platform](https://docs.rs/bevy_platform/latest/bevy_platform)
 //!     for `HashMap`
 //!   * `bevy_reflect`: Enables [Bevy Reflection](https://docs.rs/bevy_reflect/latest/bevy_reflect)
 //!     for most types
 //!   * `bevy_ecs`: Enables `Component` derives for common hexx types
+//!   * `bevy_mesh`: Enables `MeshInfo` conversion to bevy `Mesh`
 //!
 //! _Some features are enabled by default, it is recommended to enable only
 //! what is needed for your usage_
@@ -204,6 +208,19 @@
 //! - [`HexModMap<T>`](crate::storage::hexmod::HexModMap)
 //! - [`RombusMap<T>`](crate::storage::rombus::RombusMap)
 //!
+//! For unbounded maps, [`ChunkedMap<T>`](crate::storage::ChunkedMap)
+//! lazily allocates dense hexagonal chunks, and
+//! [`SparseHexagonalMap<T>`](crate::storage::SparseHexagonalMap) allows vacant
+//! coordinates in a dense hexagonal map.
+//!
+//! Boolean layers, like visibility or exploration flags, can be stored with a
+//! bit per coordinate in a [`HexBitSet`](crate::storage::HexBitSet) or a
+//! [`ChunkedHexBitSet`](crate::storage::ChunkedHexBitSet).
+//!
+//! Cellular automata, like fire spread or erosion simulations, can run on the
+//! dense storages with a double-buffered
+//! [`CellularAutomaton`](crate::storage::CellularAutomaton).
+//!
 //! ## Procedural meshes
 //!
 //! > Requires the `mesh` feature
@@ -222,27 +239,27 @@
 //!
 //! ### Usage in [Bevy](https://bevyengine.org/)
 //!
-//! If you want to integrate the procedural meshes in [bevy](bevyengine.org) you
-//! may do it this way:
+//! With the `bevy_mesh` feature (enabled by `bevy`), a
+//! [`MeshInfo`](crate::mesh::MeshInfo) converts directly into a bevy `Mesh`:
 //!
 //!```rust
-//! use bevy::{
-//!     asset::RenderAssetUsages, mesh::Indices, prelude::Mesh,
-//!     render::render_resource::PrimitiveTopology,
-//! };
-//! use hexx::MeshInfo;
-//!
-//! pub fn hexagonal_mesh(mesh_info: MeshInfo) -> Mesh {
-//!     Mesh::new(
-//!         PrimitiveTopology::TriangleList,
-//!         // Means you won't interact with the mesh on the CPU afterwards
-//!         // Check bevy docs for more information
-//!         RenderAssetUsages::RENDER_WORLD,
-//!     )
-//!     .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh_info.vertices)
-//!     .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh_info.normals)
-//!     .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, mesh_info.uvs)
-//!     .with_inserted_indices(Indices::U16(mesh_info.indices))
+//! use bevy::{asset::RenderAssetUsages, prelude::Mesh};
+//! use hexx::*;
+//!
+//! # #[cfg(feature = "bevy_mesh")]
+//! pub fn hexagonal_meshes(layout: &HexLayout) -> [Mesh; 3] {
+//!     // Keeps the mesh data available on the CPU
+//!     let column: Mesh = ColumnMeshBuilder::new(layout, 1.0).build().into();
+//!     // Means you won't interact with the mesh on the CPU afterwards
+//!     // Check bevy docs for more information
+//!     let plane = PlaneMeshBuilder::new(layout)
+//!         .build()
+//!         .into_bevy_mesh(RenderAssetUsages::RENDER_WORLD);
+//!     // Laid on the XY plane, for `Mesh2d`
+//!     let plane_2d = PlaneMeshBuilder::new(layout)
+//!         .build()
+//!         .into_bevy_mesh_2d(RenderAssetUsages::RENDER_WORLD);
+//!     [column, plane, plane_2d]
 //! }
 //! ```
 #![forbid(unsafe_code)]
@@ -269,7 +286,6 @@
 #![allow(clippy::module_name_repetitions, clippy::multiple_crate_versions)]
 // For lib.rs docs only
 #![allow(rustdoc::redundant_explicit_links)]
-/// Non exhaustive collection of classic algorithms.
 #[cfg(feature = "algorithms")]
 pub mod algorithms;
 /// Hexagonal range bounds module
@@ -290,6 +306,8 @@ pub mod orientation;
 /// Map shapes generation functions
 pub mod shapes;
 pub mod storage;
+/// Wrap-aware world topologies module
+pub mod topology;
 
 #[doc(inline)]
 pub use bounds::HexBounds;
@@ -298,10 +316,10 @@ pub use conversions::*;
 #[doc(inline)]
 pub use direction::*;
 #[doc(hidden)]
-pub use glam::{IVec2, IVec3, Quat, UVec2, Vec2, Vec3};
+pub use glam::{IVec2, IVec3, Quat, UVec2, Vec2, Vec3, Vec4};
 #[doc(inline)]
 #[cfg(feature = "grid")]
-pub use hex::{GridEdge, GridVertex};
+pub use hex::{BoundaryLoop, GridEdge, GridVertex};
 #[doc(inline)]
 pub use hex::{Hex, HexIterExt, hex};
 #[doc(inline)]
@@ -310,3 +328,5 @@ pub use layout::HexLayout;
 pub use mesh::*;
 #[doc(inline)]
 pub use orientation::HexOrientation;
+#[doc(inline)]
+pub use topology::{HexTopology, Unbounded};
@@ -0,0 +1,247 @@
+use super::MeshInfo;
+use crate::Hex;
+use glam::Vec4;
+use std::{fmt, sync::Arc};
+
+type ColorFn = dyn Fn(Hex) -> Vec4 + Send + Sync;
+type CustomVec4Fn = dyn Fn(Hex) -> Vec4 + Send + Sync;
+type CustomU32Fn = dyn Fn(Hex) -> u32 + Send + Sync;
+
+/// Extra per vertex attribute channel of a [`MeshInfo`], like an hexagon id
+/// or gameplay data for custom shaders.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum CustomAttribute {
+    /// `Vec4` values
+    Vec4(Vec<Vec4>),
+    /// `u32` values
+    U32(Vec<u32>),
+}
+
+impl CustomAttribute {
+    /// Returns the amount of values
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::Vec4(v) => v.len(),
+            Self::U32(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if there are no values
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an empty channel of the same type
+    pub(super) const fn empty_like(&self) -> Self {
+        match self {
+            Self::Vec4(_) => Self::Vec4(Vec::new()),
+            Self::U32(_) => Self::U32(Vec::new()),
+        }
+    }
+
+    /// Resizes the channel, filling new values with zeros
+    fn resize(&mut self, len: usize) {
+        match self {
+            Self::Vec4(v) => v.resize(len, Vec4::ZERO),
+            Self::U32(v) => v.resize(len, 0),
+        }
+    }
+
+    /// Appends the values of `other`
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the channels have different types
+    pub(super) fn append(&mut self, other: Self) {
+        match (self, other) {
+            (Self::Vec4(a), Self::Vec4(b)) => a.extend(b),
+            (Self::U32(a), Self::U32(b)) => a.extend(b),
+            _ => panic!("Can't merge meshes with different custom attribute types"),
+        }
+    }
+
+    /// Appends the value at `index` of `source`
+    pub(super) fn push_from(&mut self, source: &Self, index: usize) {
+        match (self, source) {
+            (Self::Vec4(a), Self::Vec4(b)) => a.push(b[index]),
+            (Self::U32(a), Self::U32(b)) => a.push(b[index]),
+            _ => unreachable!("Mismatched custom attribute types"),
+        }
+    }
+
+    /// Returns the value at `index` snapped to a grid of size `epsilon`, used
+    /// as a welding key
+    pub(super) fn snapped(&self, index: usize, epsilon: f32) -> [i64; 4] {
+        match self {
+            Self::Vec4(v) => (v[index] / epsilon)
+                .round()
+                .as_ivec4()
+                .to_array()
+                .map(i64::from),
+            Self::U32(v) => [i64::from(v[index]), 0, 0, 0],
+        }
+    }
+}
+
+#[derive(Clone)]
+enum CustomFn {
+    Vec4(Arc<CustomVec4Fn>),
+    U32(Arc<CustomU32Fn>),
+}
+
+/// Per hexagon vertex attributes of the mesh builders.
+///
+/// Every vertex generated for a hexagon gets the values returned by the
+/// callbacks for its coordinate, filling [`MeshInfo::colors`] and
+/// [`MeshInfo::custom`].
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashMap;
+/// let layout = HexLayout::default();
+/// let map: HashMap<Hex, f32> = Hex::ZERO.range(3).map(|h| (h, 1.0)).collect();
+/// // Biome colors and hexagon ids
+/// let attributes = VertexAttributes::new()
+///     .with_colors(|hex| if hex.x > 0 { Vec4::X } else { Vec4::Y })
+///     .with_custom_u32(|hex| hex.x.unsigned_abs());
+/// let mesh = HeightMapMeshBuilder::new(&layout, &map)
+///     .with_vertex_attributes(&attributes)
+///     .build();
+/// assert_eq!(mesh.colors.len(), mesh.vertices.len());
+/// ```
+#[derive(Clone, Default)]
+pub struct VertexAttributes {
+    color: Option<Arc<ColorFn>>,
+    custom: Option<CustomFn>,
+}
+
+impl VertexAttributes {
+    /// Creates empty attributes, without colors nor custom channel
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            color: None,
+            custom: None,
+        }
+    }
+
+    /// Specify the vertex colors (`Vertex_Color` attribute), as linear RGBA,
+    /// of every hexagon
+    #[must_use]
+    pub fn with_colors(mut self, color: impl Fn(Hex) -> Vec4 + Send + Sync + 'static) -> Self {
+        self.color = Some(Arc::new(color));
+        self
+    }
+
+    /// Specify a custom `Vec4` channel value of every hexagon, replacing any
+    /// previous custom channel
+    #[must_use]
+    pub fn with_custom_vec4(mut self, value: impl Fn(Hex) -> Vec4 + Send + Sync + 'static) -> Self {
+        self.custom = Some(CustomFn::Vec4(Arc::new(value)));
+        self
+    }
+
+    /// Specify a custom `u32` channel value of every hexagon, replacing any
+    /// previous custom channel
+    #[must_use]
+    pub fn with_custom_u32(mut self, value: impl Fn(Hex) -> u32 + Send + Sync + 'static) -> Self {
+        self.custom = Some(CustomFn::U32(Arc::new(value)));
+        self
+    }
+
+    /// Returns `true` if no attribute is specified
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.color.is_none() && self.custom.is_none()
+    }
+
+    /// Appends the attribute values of `hex` for `count` new vertices of
+    /// `mesh`
+    pub(crate) fn append(&self, mesh: &mut MeshInfo, hex: Hex, count: usize) {
+        if let Some(color) = &self.color {
+            let color = color(hex);
+            mesh.colors.extend(std::iter::repeat_n(color, count));
+        }
+        match &self.custom {
+            Some(CustomFn::Vec4(f)) => {
+                let value = f(hex);
+                let channel = mesh
+                    .custom
+                    .get_or_insert_with(|| CustomAttribute::Vec4(Vec::new()));
+                if let CustomAttribute::Vec4(v) = channel {
+                    v.extend(std::iter::repeat_n(value, count));
+                }
+            }
+            Some(CustomFn::U32(f)) => {
+                let value = f(hex);
+                let channel = mesh
+                    .custom
+                    .get_or_insert_with(|| CustomAttribute::U32(Vec::new()));
+                if let CustomAttribute::U32(v) = channel {
+                    v.extend(std::iter::repeat_n(value, count));
+                }
+            }
+            None => (),
+        }
+    }
+
+    /// Sets the attribute values of every vertex of `mesh` to the values of
+    /// `hex`
+    pub(crate) fn apply(&self, mesh: &mut MeshInfo, hex: Hex) {
+        if self.color.is_some() {
+            mesh.colors.clear();
+        }
+        if self.custom.is_some() {
+            mesh.custom = None;
+        }
+        self.append(mesh, hex, mesh.vertices.len());
+    }
+}
+
+impl fmt::Debug for VertexAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let custom = match self.custom {
+            Some(CustomFn::Vec4(_)) => Some("Vec4"),
+            Some(CustomFn::U32(_)) => Some("U32"),
+            None => None,
+        };
+        f.debug_struct("VertexAttributes")
+            .field("color", &self.color.is_some())
+            .field("custom", &custom)
+            .finish()
+    }
+}
+
+impl MeshInfo {
+    /// Pads the colors and custom channel of `self` and `rhs` before merging,
+    /// so that both cover all their vertices.
+    ///
+    /// Missing colors are white, missing custom values are zeros
+    pub(super) fn pad_attributes(&mut self, rhs: &mut Self) {
+        if !self.colors.is_empty() || !rhs.colors.is_empty() {
+            self.colors.resize(self.vertices.len(), Vec4::ONE);
+            rhs.colors.resize(rhs.vertices.len(), Vec4::ONE);
+        }
+        let empty = self
+            .custom
+            .as_ref()
+            .or(rhs.custom.as_ref())
+            .map(CustomAttribute::empty_like);
+        if let Some(empty) = empty {
+            for mesh in [self, rhs] {
+                let len = mesh.vertices.len();
+                mesh.custom.get_or_insert_with(|| empty.clone()).resize(len);
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::{CustomAttribute, MeshIndices, MeshInfo};
+use bevy_asset::RenderAssetUsages;
+use bevy_mesh::{Indices, Mesh, MeshVertexAttribute, PrimitiveTopology, VertexFormat};
+use glam::Vec3Swizzles;
+
+impl MeshInfo {
+    /// Vertex attribute of a [`CustomAttribute::Vec4`] channel in a bevy
+    /// [`Mesh`], at the `Vertex_Custom` location of custom shaders
+    pub const ATTRIBUTE_CUSTOM_VEC4: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_Custom", 0x4845_5858_0001, VertexFormat::Float32x4);
+    /// Vertex attribute of a [`CustomAttribute::U32`] channel in a bevy
+    /// [`Mesh`], at the `Vertex_Custom` location of custom shaders
+    pub const ATTRIBUTE_CUSTOM_U32: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_Custom", 0x4845_5858_0002, VertexFormat::Uint32);
+
+    /// Converts the mesh into a bevy [`Mesh`] with the given `asset_usage`.
+    ///
+    /// Positions, normals and uvs are always inserted, tangents, colors and
+    /// the custom channel (See [`Self::ATTRIBUTE_CUSTOM_VEC4`] and
+    /// [`Self::ATTRIBUTE_CUSTOM_U32`]) are inserted if set for every vertex.
+    /// Indices keep their `u16` or `u32` width.
+    ///
+    /// > Use [`RenderAssetUsages::RENDER_WORLD`] if you won't interact with the
+    /// > mesh on the CPU afterwards
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// use bevy::{asset::RenderAssetUsages, mesh::Indices};
+    ///
+    /// let info = ColumnMeshBuilder::new(&HexLayout::default(), 1.0).build();
+    /// let mesh = info.into_bevy_mesh(RenderAssetUsages::RENDER_WORLD);
+    /// assert!(matches!(mesh.indices(), Some(Indices::U16(_))));
+    /// ```
+    #[must_use]
+    pub fn into_bevy_mesh(self, asset_usage: RenderAssetUsages) -> Mesh {
+        let count = self.vertices.len();
+        let has_tangents = self.has_tangents();
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.vertices)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+            .with_inserted_indices(self.indices.into());
+        if has_tangents {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, self.tangents);
+        }
+        if self.colors.len() == count && count > 0 {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, self.colors);
+        }
+        match self.custom {
+            Some(CustomAttribute::Vec4(v)) if v.len() == count => {
+                mesh.insert_attribute(Self::ATTRIBUTE_CUSTOM_VEC4, v);
+            }
+            Some(CustomAttribute::U32(v)) if v.len() == count => {
+                mesh.insert_attribute(Self::ATTRIBUTE_CUSTOM_U32, v);
+            }
+            _ => (),
+        }
+        mesh
+    }
+
+    /// Converts the mesh into a bevy [`Mesh`] for `Mesh2d`, with the given
+    /// `asset_usage`.
+    ///
+    /// The mesh is expected to use the default `Y` up facing of the mesh
+    /// builders, and is laid on the `XY` plane facing `Z`: a 3D vertex
+    /// `(x, y, z)` becomes `(x, z, y)` so that vertex positions match
+    /// [`HexLayout::hex_to_world_pos`](crate::HexLayout::hex_to_world_pos).
+    /// The triangle winding, normals and tangents are adjusted accordingly.
+    ///
+    /// > See [`Self::into_bevy_mesh`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// use bevy::{asset::RenderAssetUsages, mesh::Mesh};
+    ///
+    /// let layout = HexLayout::default();
+    /// let hex = hex(1, 2);
+    /// let info = PlaneMeshBuilder::new(&layout).at(hex).build();
+    /// let mesh = info.into_bevy_mesh_2d(RenderAssetUsages::default());
+    /// let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION).unwrap();
+    /// assert_eq!(positions.len(), 6);
+    /// ```
+    #[must_use]
+    pub fn into_bevy_mesh_2d(mut self, asset_usage: RenderAssetUsages) -> Mesh {
+        for v in self.vertices.iter_mut().chain(&mut self.normals) {
+            *v = v.xzy();
+        }
+        for t in &mut self.tangents {
+            *t = t.truncate().xzy().extend(-t.w);
+        }
+        // Swapping two axis mirrors the mesh, so we restore the triangles
+        // counter clockwise order
+        self.indices = match self.indices {
+            MeshIndices::U16(mut v) => {
+                v.chunks_exact_mut(3).for_each(|t| t.swap(1, 2));
+                MeshIndices::U16(v)
+            }
+            MeshIndices::U32(mut v) => {
+                v.chunks_exact_mut(3).for_each(|t| t.swap(1, 2));
+                MeshIndices::U32(v)
+            }
+        };
+        self.into_bevy_mesh(asset_usage)
+    }
+}
+
+impl From<MeshIndices> for Indices {
+    fn from(value: MeshIndices) -> Self {
+        match value {
+            MeshIndices::U16(v) => Self::U16(v),
+            MeshIndices::U32(v) => Self::U32(v),
+        }
+    }
+}
+
+impl From<MeshInfo> for Mesh {
+    /// Converts the mesh with the default [`RenderAssetUsages`], keeping the
+    /// mesh data in both the main and render worlds.
+    ///
+    /// > See [`MeshInfo::into_bevy_mesh`]
+    fn from(value: MeshInfo) -> Self {
+        value.into_bevy_mesh(RenderAssetUsages::default())
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{BASE_FACING, BoundaryLoop, Hex, HexLayout, MeshInfo, UVOptions};
+use glam::{Quat, Vec2, Vec3};
+
+/// Builder struct to customize border (outline) mesh generation around a
+/// region of hexagonal coordinates.
+///
+/// The mesh is a flat ribbon following every [`BoundaryLoop`] of the region,
+/// outer boundaries and holes, with mitered corners at the
+/// [`GridVertex`](crate::GridVertex) junctions.
+///
+/// # UV mapping
+///
+/// * The `u` coordinate follows the length of the ribbon, from `0.0` to `1.0`
+///   along each loop, or repeating every [`Self::uv_repeat_length`] world units
+/// * The `v` coordinate goes across the ribbon, from `0.0` on the outer side to
+///   `1.0` on the inner side
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// let layout = HexLayout::default();
+/// // Outline of a territory, drawn just inside the region
+/// let mesh = BorderMeshBuilder::new(&layout, Hex::ZERO.range(3))
+///     .with_width(0.2)
+///     .with_inset(0.1)
+///     .with_vertical_offset(0.01)
+///     .build();
+/// ```
+///
+/// # Notes
+///
+/// Transform operations (Scale, Rotate, Translate) through the methods
+///
+/// - Scale: [`Self::with_scale`]
+/// - Rotate: [`Self::with_rotation`], [`Self::facing`]
+/// - Translate: [`Self::with_offset`]
+///
+/// Are executed in that order, or **SRT**
+#[derive(Debug, Clone)]
+pub struct BorderMeshBuilder<'l> {
+    /// The hexagonal layout, used to compute vertex positions
+    pub layout: &'l HexLayout,
+    /// The boundary loops to follow
+    pub loops: Vec<BoundaryLoop>,
+    /// The ribbon width, in world units
+    pub width: f32,
+    /// Distance between the region boundary and the ribbon center line, in
+    /// world units.
+    ///
+    /// Positive values move the ribbon inside the region, negative values move
+    /// it outside. `0.0` centers the ribbon on the boundary.
+    pub inset: f32,
+    /// Vertical offset of the ribbon, applied before any transformation
+    pub vertical_offset: f32,
+    /// If set, the `u` coordinate repeats every `uv_repeat_length` world
+    /// units instead of spanning each loop
+    pub uv_repeat_length: Option<f32>,
+    /// UV mapping options
+    pub uv_options: UVOptions,
+    /// Optional custom offset for the mesh vertex positions
+    pub offset: Option<Vec3>,
+    /// Optional custom scale factor for the mesh vertex positions
+    pub scale: Option<Vec3>,
+    /// Optional custom rotation, useful to have the mesh already
+    /// rotated
+    ///
+    /// By default the mesh is *facing* up (**Y** axis)
+    pub rotation: Option<Quat>,
+    /// If set to `true`, the mesh will ignore [`HexLayout::origin`]
+    pub center_aligned: bool,
+    /// If set to `true`, vertex tangents are computed for normal mapping
+    pub tangents: bool,
+}
+
+impl<'l> BorderMeshBuilder<'l> {
+    /// Setup a new builder using the given `layout`, following the boundary
+    /// of the region made of `coords`
+    #[must_use]
+    pub fn new(layout: &'l HexLayout, coords: impl IntoIterator<Item = Hex>) -> Self {
+        Self::from_loops(layout, BoundaryLoop::from_region(coords))
+    }
+
+    /// Setup a new builder using the given `layout`, following the given
+    /// boundary `loops`
+    #[must_use]
+    pub const fn from_loops(layout: &'l HexLayout, loops: Vec<BoundaryLoop>) -> Self {
+        Self {
+            layout,
+            loops,
+            width: 0.1,
+            inset: 0.0,
+            vertical_offset: 0.0,
+            uv_repeat_length: None,
+            uv_options: UVOptions::new(),
+            offset: None,
+            scale: None,
+            rotation: None,
+            center_aligned: false,
+            tangents: false,
+        }
+    }
+
+    /// Specify the ribbon width, in world units. (Default is `0.1`)
+    #[must_use]
+    pub const fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Specify the distance between the region boundary and the ribbon center
+    /// line. Positive values move the ribbon inwards, negative values outwards
+    #[must_use]
+    pub const fn with_inset(mut self, inset: f32) -> Self {
+        self.inset = inset;
+        self
+    }
+
+    /// Specify a vertical offset for the ribbon, useful to avoid z-fighting
+    /// with the underlying hexagons
+    #[must_use]
+    pub const fn with_vertical_offset(mut self, offset: f32) -> Self {
+        self.vertical_offset = offset;
+        self
+    }
+
+    /// Specify a length, in world units, after which the `u` coordinate
+    /// repeats, allowing tiled textures
+    #[must_use]
+    pub const fn with_uv_repeat_length(mut self, length: f32) -> Self {
+        self.uv_repeat_length = Some(length);
+        self
+    }
+
+    /// Specify custom UV mapping options
+    #[must_use]
+    pub const fn with_uv_options(mut self, uv_options: UVOptions) -> Self {
+        self.uv_options = uv_options;
+        self
+    }
+
+    /// Specify a custom *facing* direction for the mesh, by default the mesh
+    /// is horizontal (facing up)
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `facing` is zero length
+    #[must_use]
+    pub fn facing(mut self, facing: Vec3) -> Self {
+        self.rotation = Some(Quat::from_rotation_arc(BASE_FACING, facing.normalize()));
+        self
+    }
+
+    /// Specify a custom rotation for the whole mesh
+    #[must_use]
+    pub const fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    /// Specify a custom offset for the whole mesh
+    #[must_use]
+    pub const fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Specify a custom scale factor for the whole mesh
+    #[must_use]
+    pub const fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    #[must_use]
+    #[inline]
+    /// Ignores the [`HexLayout::origin`] offset, generating a mesh centered
+    /// around `(0.0, 0.0)`.
+    pub const fn center_aligned(mut self) -> Self {
+        self.center_aligned = true;
+        self
+    }
+
+    /// Computes the vertex tangents, with handedness, for normal mapped
+    /// materials (See [`MeshInfo::compute_tangents`])
+    #[must_use]
+    #[inline]
+    pub const fn with_tangents(mut self) -> Self {
+        self.tangents = true;
+        self
+    }
+
+    /// Computes the ribbon of a single loop
+    fn loop_mesh(&self, boundary: &BoundaryLoop) -> MeshInfo {
+        let len = boundary.edges.len();
+        if len == 0 {
+            return MeshInfo::default();
+        }
+        let origin = if self.center_aligned {
+            self.layout.origin
+        } else {
+            Vec2::ZERO
+        };
+        let points: Vec<Vec2> = self
+            .layout
+            .boundary_coordinates(boundary)
+            .into_iter()
+            .map(|p| p - origin)
+            .collect();
+        // Unit normals of every edge, pointing inside the region
+        let normals: Vec<Vec2> = boundary
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(i, edge)| {
+                let [a, b] = [points[i], points[(i + 1) % len]];
+                let normal = (b - a).perp().normalize();
+                let center = self.layout.hex_to_world_pos(edge.origin) - origin;
+                if normal.dot(center - a) < 0.0 {
+                    -normal
+                } else {
+                    normal
+                }
+            })
+            .collect();
+        let [outer, inner] = [self.inset - self.width / 2.0, self.inset + self.width / 2.0];
+        let mut mesh = MeshInfo::default();
+        let mut distance = 0.0;
+        let mut previous_center: Option<Vec2> = None;
+        let mut centers = Vec::with_capacity(len + 1);
+        // The first vertex is repeated to close the loop with a UV seam
+        for i in 0..=len {
+            let index = i % len;
+            let point = points[index];
+            let normal = normals[index];
+            let previous_normal = normals[(index + len - 1) % len];
+            // Miter direction and scale, keeping the ribbon width constant
+            let miter = (normal + previous_normal).normalize_or(normal);
+            let miter = miter / miter.dot(normal);
+            let center = point + miter * self.inset;
+            if let Some(previous) = previous_center {
+                distance += previous.distance(center);
+            }
+            previous_center = Some(center);
+            centers.push(distance);
+            for (side, v) in [(outer, 0.0), (inner, 1.0)] {
+                let pos = point + miter * side;
+                mesh.vertices
+                    .push(Vec3::new(pos.x, self.vertical_offset, pos.y));
+                mesh.normals.push(BASE_FACING);
+                mesh.uvs.push(Vec2::new(0.0, v));
+            }
+        }
+        let total = distance;
+        for (i, d) in centers.into_iter().enumerate() {
+            let u = self
+                .uv_repeat_length
+                .map_or_else(|| d / total, |length| d / length);
+            mesh.uvs[i * 2].x = u;
+            mesh.uvs[i * 2 + 1].x = u;
+        }
+        let vertex_count =
+            u32::try_from(mesh.vertices.len()).expect("MeshInfo has too many vertices");
+        for i in (0..vertex_count - 2).step_by(2) {
+            let [outer_a, inner_a, outer_b, inner_b] = [i, i + 1, i + 2, i + 3];
+            mesh.indices
+                .extend([outer_a, inner_a, outer_b, inner_a, inner_b, outer_b]);
+        }
+        mesh
+    }
+
+    /// Comsumes the builder to return the computed mesh data
+    #[must_use]
+    pub fn build(self) -> MeshInfo {
+        let mut mesh = MeshInfo::default();
+        for boundary in &self.loops {
+            mesh.merge_with(self.loop_mesh(boundary));
+        }
+        // **S** - We apply optional scale
+        if let Some(scale) = self.scale {
+            mesh = mesh.with_scale(scale);
+        }
+        // **R** - We rotate the mesh to face the given direction
+        if let Some(rotation) = self.rotation {
+            mesh = mesh.rotated(rotation);
+        }
+        // **T** - We offset the vertex positions after scaling and rotating
+        if let Some(offset) = self.offset {
+            mesh = mesh.with_offset(offset);
+        }
+        self.uv_options.alter_uvs(&mut mesh.uvs);
+        if self.tangents {
+            mesh.compute_tangents();
+        }
+        mesh
+    }
+}
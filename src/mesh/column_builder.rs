@@ -3,6 +3,7 @@ use glam::{Quat, Vec3};
 use super::{BASE_FACING, MeshInfo, face::Quad};
 use crate::{
     EdgeDirection, FaceOptions, Hex, HexLayout, InsetOptions, PlaneMeshBuilder, UVOptions,
+    VertexAttributes,
 };
 
 /// Builder struct to customize hex column mesh generation.
@@ -65,6 +66,10 @@ pub struct ColumnMeshBuilder<'l> {
     pub sides_options: [Option<FaceOptions>; 6],
     /// If set to `true`, the mesh will ignore [`HexLayout::origin`]
     pub center_aligned: bool,
+    /// If set to `true`, vertex tangents are computed for normal mapping
+    pub tangents: bool,
+    /// Per hexagon vertex colors and custom attribute
+    pub attributes: Option<&'l VertexAttributes>,
 }
 
 impl<'l> ColumnMeshBuilder<'l> {
@@ -83,6 +88,8 @@ impl<'l> ColumnMeshBuilder<'l> {
             bottom_face: Some(PlaneMeshBuilder::new(layout)),
             sides_options: [Some(FaceOptions::new()); 6],
             center_aligned: false,
+            tangents: false,
+            attributes: None,
         }
     }
 
@@ -262,6 +269,23 @@ impl<'l> ColumnMeshBuilder<'l> {
         self
     }
 
+    /// Computes the vertex tangents, with handedness, for normal mapped
+    /// materials (See [`MeshInfo::compute_tangents`])
+    #[must_use]
+    #[inline]
+    pub const fn with_tangents(mut self) -> Self {
+        self.tangents = true;
+        self
+    }
+
+    /// Specify per hexagon vertex colors and custom attribute values
+    #[must_use]
+    #[inline]
+    pub const fn with_vertex_attributes(mut self, attributes: &'l VertexAttributes) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+
     #[must_use]
     #[expect(clippy::cast_precision_loss)]
     /// Comsumes the builder to return the computed mesh data
@@ -324,6 +348,12 @@ impl<'l> ColumnMeshBuilder<'l> {
             offset += custom_offset;
         }
         mesh = mesh.with_offset(offset);
+        if let Some(attributes) = self.attributes {
+            attributes.apply(&mut mesh, self.pos);
+        }
+        if self.tangents {
+            mesh.compute_tangents();
+        }
         mesh
     }
 }
@@ -0,0 +1,320 @@
+use super::{MeshIndices, MeshInfo};
+use glam::{Vec2, Vec3, Vec4};
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+/// `glTF` component type of `f32`
+const GLTF_FLOAT: u32 = 5126;
+/// `glTF` component type of `u16`
+const GLTF_UNSIGNED_SHORT: u32 = 5123;
+/// `glTF` component type of `u32`
+const GLTF_UNSIGNED_INT: u32 = 5125;
+/// `glTF` buffer view target of vertex attributes
+const GLTF_ARRAY_BUFFER: u32 = 34962;
+/// `glTF` buffer view target of indices
+const GLTF_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+impl MeshInfo {
+    /// Writes the mesh in the Wavefront OBJ text format.
+    ///
+    /// Positions and faces are written, along with the normals and uvs if set
+    /// for every vertex. Vertex colors, if set for every vertex, are appended
+    /// to the `v` lines (`v x y z r g b`) as supported by most softwares. The
+    /// uv `v` coordinate is flipped as OBJ uvs start at the bottom left
+    /// corner.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of the `writer`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let mesh = PlaneMeshBuilder::new(&HexLayout::default()).build();
+    /// let mut obj = Vec::new();
+    /// mesh.write_obj(&mut obj).unwrap();
+    /// let obj = String::from_utf8(obj).unwrap();
+    /// assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 4);
+    /// ```
+    pub fn write_obj(&self, mut writer: impl Write) -> io::Result<()> {
+        let count = self.vertices.len();
+        let [has_uvs, has_normals, has_colors] = [
+            self.uvs.len() == count,
+            self.normals.len() == count,
+            self.colors.len() == count,
+        ];
+        writeln!(writer, "# hexx mesh")?;
+        for (i, v) in self.vertices.iter().enumerate() {
+            if has_colors {
+                let c = self.colors[i];
+                writeln!(writer, "v {} {} {} {} {} {}", v.x, v.y, v.z, c.x, c.y, c.z)?;
+            } else {
+                writeln!(writer, "v {} {} {}", v.x, v.y, v.z)?;
+            }
+        }
+        if has_uvs {
+            for uv in &self.uvs {
+                writeln!(writer, "vt {} {}", uv.x, 1.0 - uv.y)?;
+            }
+        }
+        if has_normals {
+            for n in &self.normals {
+                writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+            }
+        }
+        // Face vertices reference the available channels only
+        let vertex = |i: u32| match (has_uvs, has_normals) {
+            (true, true) => format!("{i}/{i}/{i}"),
+            (true, false) => format!("{i}/{i}"),
+            (false, true) => format!("{i}//{i}"),
+            (false, false) => i.to_string(),
+        };
+        let indices: Vec<u32> = self.indices.iter().collect();
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| vertex(i + 1));
+            writeln!(writer, "f {a} {b} {c}")?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the mesh in the binary little endian PLY format.
+    ///
+    /// Positions and faces are written, along with the normals, uvs (`s`, `t`)
+    /// and colors (as `u8` RGBA) if set for every vertex.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of the `writer`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let mesh = PlaneMeshBuilder::new(&HexLayout::default()).build();
+    /// let mut ply = Vec::new();
+    /// mesh.write_ply(&mut ply).unwrap();
+    /// assert!(ply.starts_with(b"ply\nformat binary_little_endian 1.0\n"));
+    /// ```
+    pub fn write_ply(&self, mut writer: impl Write) -> io::Result<()> {
+        let count = self.vertices.len();
+        let [has_normals, has_uvs, has_colors] = [
+            self.normals.len() == count,
+            self.uvs.len() == count,
+            self.colors.len() == count,
+        ];
+        let triangles = self.indices.len() / 3;
+        writeln!(
+            writer,
+            "ply\nformat binary_little_endian 1.0\ncomment hexx mesh"
+        )?;
+        writeln!(writer, "element vertex {count}")?;
+        let mut properties = vec!["x", "y", "z"];
+        if has_normals {
+            properties.extend(["nx", "ny", "nz"]);
+        }
+        if has_uvs {
+            properties.extend(["s", "t"]);
+        }
+        for property in properties {
+            writeln!(writer, "property float {property}")?;
+        }
+        if has_colors {
+            for property in ["red", "green", "blue", "alpha"] {
+                writeln!(writer, "property uchar {property}")?;
+            }
+        }
+        writeln!(writer, "element face {triangles}")?;
+        writeln!(
+            writer,
+            "property list uchar uint vertex_indices\nend_header"
+        )?;
+        for (i, v) in self.vertices.iter().enumerate() {
+            let normal = has_normals.then(|| self.normals[i].to_array());
+            let uv = has_uvs.then(|| self.uvs[i].to_array());
+            let values = v
+                .to_array()
+                .into_iter()
+                .chain(normal.into_iter().flatten())
+                .chain(uv.into_iter().flatten());
+            for value in values {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            if has_colors {
+                writer.write_all(&color_to_bytes(self.colors[i]))?;
+            }
+        }
+        let indices: Vec<u32> = self.indices.iter().collect();
+        for triangle in indices.chunks_exact(3) {
+            writer.write_all(&[3])?;
+            for index in triangle {
+                writer.write_all(&index.to_le_bytes())?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Writes the mesh as a minimal binary glTF 2.0 (`.glb`) file, with a
+    /// single node and mesh.
+    ///
+    /// Positions, normals, uvs, tangents and colors are written as vertex
+    /// attributes if set for every vertex, with `u16` or `u32` indices
+    /// (See [`MeshIndices`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of the `writer`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let mesh = ColumnMeshBuilder::new(&HexLayout::default(), 1.0).build();
+    /// let mut glb = Vec::new();
+    /// mesh.write_glb(&mut glb).unwrap();
+    /// assert!(glb.starts_with(b"glTF"));
+    /// ```
+    pub fn write_glb(&self, mut writer: impl Write) -> io::Result<()> {
+        let (json, buffer) = self.gltf_content();
+        let mut json = json.into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let to_u32 = |len: usize| {
+            u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        };
+        let binary_chunk_len = if buffer.is_empty() {
+            0
+        } else {
+            8 + buffer.len()
+        };
+        // Header
+        writer.write_all(b"glTF")?;
+        writer.write_all(&2_u32.to_le_bytes())?;
+        writer.write_all(&to_u32(12 + 8 + json.len() + binary_chunk_len)?.to_le_bytes())?;
+        // JSON chunk
+        writer.write_all(&to_u32(json.len())?.to_le_bytes())?;
+        writer.write_all(b"JSON")?;
+        writer.write_all(&json)?;
+        // Binary chunk
+        if !buffer.is_empty() {
+            writer.write_all(&to_u32(buffer.len())?.to_le_bytes())?;
+            writer.write_all(b"BIN\0")?;
+            writer.write_all(&buffer)?;
+        }
+        writer.flush()
+    }
+
+    /// Computes the `glTF` JSON document and its binary buffer
+    fn gltf_content(&self) -> (String, Vec<u8>) {
+        let count = self.vertices.len();
+        let mut fields = vec![r#""asset":{"version":"2.0","generator":"hexx"}"#.to_owned()];
+        if count == 0 {
+            return (format!("{{{}}}", fields.join(",")), Vec::new());
+        }
+        let mut buffer = Vec::new();
+        let mut views = Vec::new();
+        let mut accessors = Vec::new();
+        let mut push_accessor = |bytes: Vec<u8>, target: u32, accessor: String| {
+            let offset = buffer.len();
+            let length = bytes.len();
+            buffer.extend(bytes);
+            buffer.resize(buffer.len().next_multiple_of(4), 0);
+            views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{length},"target":{target}}}"#
+            ));
+            let view = views.len() - 1;
+            accessors.push(format!(r#"{{"bufferView":{view},{accessor}}}"#));
+            accessors.len() - 1
+        };
+        let bounds = self
+            .vertices
+            .iter()
+            .fold([Vec3::MAX, Vec3::MIN], |[min, max], v| {
+                [min.min(*v), max.max(*v)]
+            });
+        let mut vertex_attributes = vec![("POSITION", "VEC3", vec3_bytes(&self.vertices))];
+        if self.normals.len() == count {
+            vertex_attributes.push(("NORMAL", "VEC3", vec3_bytes(&self.normals)));
+        }
+        if self.uvs.len() == count {
+            vertex_attributes.push(("TEXCOORD_0", "VEC2", vec2_bytes(&self.uvs)));
+        }
+        if self.has_tangents() {
+            vertex_attributes.push(("TANGENT", "VEC4", vec4_bytes(&self.tangents)));
+        }
+        if self.colors.len() == count {
+            vertex_attributes.push(("COLOR_0", "VEC4", vec4_bytes(&self.colors)));
+        }
+        let mut attributes = Vec::new();
+        for (name, kind, bytes) in vertex_attributes {
+            let mut accessor =
+                format!(r#""componentType":{GLTF_FLOAT},"count":{count},"type":"{kind}""#);
+            if name == "POSITION" {
+                let [min, max] = bounds.map(|v| format!("[{},{},{}]", v.x, v.y, v.z));
+                let _ = write!(accessor, r#","min":{min},"max":{max}"#);
+            }
+            let id = push_accessor(bytes, GLTF_ARRAY_BUFFER, accessor);
+            attributes.push(format!(r#""{name}":{id}"#));
+        }
+        let mut primitive = format!(r#""attributes":{{{}}}"#, attributes.join(","));
+        if !self.indices.is_empty() {
+            let (bytes, component) = match &self.indices {
+                MeshIndices::U16(v) => (
+                    v.iter().flat_map(|i| i.to_le_bytes()).collect(),
+                    GLTF_UNSIGNED_SHORT,
+                ),
+                MeshIndices::U32(v) => (
+                    v.iter().flat_map(|i| i.to_le_bytes()).collect(),
+                    GLTF_UNSIGNED_INT,
+                ),
+            };
+            let accessor = format!(
+                r#""componentType":{component},"count":{},"type":"SCALAR""#,
+                self.indices.len()
+            );
+            let id = push_accessor(bytes, GLTF_ELEMENT_ARRAY_BUFFER, accessor);
+            let _ = write!(primitive, r#","indices":{id}"#);
+        }
+        fields.extend([
+            r#""scene":0,"scenes":[{"nodes":[0]}],"nodes":[{"mesh":0}]"#.to_owned(),
+            format!(r#""meshes":[{{"primitives":[{{{primitive}}}]}}]"#),
+            format!(r#""accessors":[{}]"#, accessors.join(",")),
+            format!(r#""bufferViews":[{}]"#, views.join(",")),
+            format!(r#""buffers":[{{"byteLength":{}}}]"#, buffer.len()),
+        ]);
+        (format!("{{{}}}", fields.join(",")), buffer)
+    }
+}
+
+fn vec2_bytes(values: &[Vec2]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(Vec2::to_array)
+        .flat_map(f32::to_le_bytes)
+        .collect()
+}
+
+fn vec3_bytes(values: &[Vec3]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(Vec3::to_array)
+        .flat_map(f32::to_le_bytes)
+        .collect()
+}
+
+fn vec4_bytes(values: &[Vec4]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(Vec4::to_array)
+        .flat_map(f32::to_le_bytes)
+        .collect()
+}
+
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn color_to_bytes(color: Vec4) -> [u8; 4] {
+    (color.clamp(Vec4::ZERO, Vec4::ONE) * 255.0)
+        .round()
+        .to_array()
+        .map(|c| c as u8)
+}
@@ -1,4 +1,4 @@
-use crate::{BASE_FACING, HexLayout, InsetScaleMode, MeshInfo, UVOptions};
+use crate::{BASE_FACING, HexLayout, InsetScaleMode, MeshIndices, MeshInfo, UVOptions};
 use glam::{Vec2, Vec3};
 
 use super::FaceOptions;
@@ -217,7 +217,7 @@ impl<const VERTS: usize, const TRIS: usize> Face<VERTS, TRIS> {
             }
             a.0.into_iter().chain(b.0)
         });
-        mesh.indices.extend(connection_indices);
+        mesh.indices.extend(connection_indices.map(u32::from));
         mesh.merge_with(inset_face);
         mesh
     }
@@ -229,7 +229,10 @@ impl<const VERTS: usize, const TRIS: usize> From<Face<VERTS, TRIS>> for MeshInfo
             vertices: face.positions.to_vec(),
             normals: face.normals.to_vec(),
             uvs: face.uvs.to_vec(),
-            indices: face.triangles.into_iter().flat_map(|t| t.0).collect(),
+            tangents: Vec::new(),
+            colors: Vec::new(),
+            custom: None,
+            indices: MeshIndices::U16(face.triangles.into_iter().flat_map(|t| t.0).collect()),
         }
     }
 }
@@ -1,12 +1,77 @@
 use super::{FaceOptions, InsetOptions, MeshInfo, face::Quad};
-use crate::{EdgeDirection, Hex, HexLayout, PlaneMeshBuilder, UVOptions, storage::HexStore};
-use glam::{Quat, Vec3};
+use crate::{
+    EdgeDirection, Hex, HexLayout, PlaneMeshBuilder, UVOptions, VertexAttributes, VertexDirection,
+    storage::HexStore,
+};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+use glam::{Quat, Vec2, Vec3};
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
 use std::{ops::RangeInclusive, sync::Arc};
 
 type MapFringeHeightFn = dyn Fn(Hex) -> f32;
+
+/// Tolerance used to share smooth normals and weld vertices
+const SMOOTH_EPSILON: f32 = 1e-4;
+
+/// Smooth terrain triangle positions, uvs and source coordinate
+type SmoothTriangle = ([Vec3; 3], [Vec2; 3], Hex);
 type CapOptionsFn = dyn Fn(Hex) -> Option<FaceOptions>;
 type SideOptionsFn = dyn Fn(Hex, Hex) -> Option<FaceOptions>;
 
+/// Smooth terrain options for [`HeightMapMeshBuilder`]
+///
+/// In smooth mode the heights of the hexagon corners are averaged with the
+/// two neighboring coordinates sharing the corner, generating sloped
+/// triangles between hexagons instead of terraced columns.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct SmoothOptions {
+    /// Amount of subdivisions of each of the 6 hexagon triangles.
+    ///
+    /// With no subdivision the hexagon is a simple fan of 6 sloped triangles,
+    /// subdividing it allows a rounder transition between the hexagon center
+    /// and its edges
+    pub subdivisions: u32,
+    /// If set to `true` vertex normals are averaged with the adjacent
+    /// triangles, including the ones of neighboring hexagons. Otherwise every
+    /// triangle is flat shaded
+    pub smooth_normals: bool,
+}
+
+impl SmoothOptions {
+    /// Default smooth options, without subdivisions and with smooth normals
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            subdivisions: 0,
+            smooth_normals: true,
+        }
+    }
+
+    /// Specify the amount of subdivisions of each hexagon triangle
+    #[must_use]
+    pub const fn with_subdivisions(mut self, subdivisions: u32) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+
+    /// Uses flat normals, giving a faceted look
+    #[must_use]
+    pub const fn with_flat_normals(mut self) -> Self {
+        self.smooth_normals = false;
+        self
+    }
+}
+
+impl Default for SmoothOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Builder struct to customize hex column heightmap mesh generation.
 ///
 /// # Example
@@ -48,6 +113,22 @@ type SideOptionsFn = dyn Fn(Hex, Hex) -> Option<FaceOptions>;
 ///   typically in the case of a large heightmap which is divided in smaller
 ///   meshes and you wish for all those meshes to connect seamlessly
 ///
+/// # Smooth terrain
+///
+/// Instead of terraced columns, the builder can generate a continuous
+/// terrain using [`Self::with_smoothing`] (See [`SmoothOptions`])
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashMap;
+///
+/// let map: HashMap<Hex, f32> = Hex::ZERO.range(5).map(|h| (h, h.x as f32)).collect();
+/// let layout = HexLayout::default();
+/// let mesh = HeightMapMeshBuilder::new(&layout, &map)
+///     .with_smoothing(SmoothOptions::new().with_subdivisions(2))
+///     .build();
+/// ```
+///
 /// # Notes
 ///
 /// Transform operations (Scale, Rotate, Translate) through the methods
@@ -79,6 +160,10 @@ pub struct HeightMapMeshBuilder<'l, 'm, HeightMap> {
     pub rotation: Option<Quat>,
     /// If set to `true`, the mesh will ignore [`HexLayout::origin`]
     pub center_aligned: bool,
+    /// If set to `true`, vertex tangents are computed for normal mapping
+    pub tangents: bool,
+    /// Per hexagon vertex colors and custom attribute
+    pub attributes: Option<&'l VertexAttributes>,
     /// Specifies the height for side quads to be generated at the fringe
     /// of the `map` (Map edge and potential holes in sparse maps).
     ///
@@ -91,6 +176,9 @@ pub struct HeightMapMeshBuilder<'l, 'm, HeightMap> {
     /// Optional function pointer to specify custom [`FaceOptions`] for some
     /// side quads.
     pub custom_sides_options: Option<Arc<SideOptionsFn>>,
+    /// Optional smooth terrain options. If set, a continuous terrain is
+    /// generated instead of hexagonal columns
+    pub smoothing: Option<SmoothOptions>,
 }
 
 impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
@@ -117,9 +205,12 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
             scale: None,
             rotation: None,
             center_aligned: false,
+            tangents: false,
+            attributes: None,
             fringe_heights: None,
             custom_caps_options: None,
             custom_sides_options: None,
+            smoothing: None,
         }
     }
 
@@ -189,7 +280,7 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
     ///
     /// Notes:
     /// * This won't have any effect if [`Self::top_face_options`] is disabled
-    ///   by [`Self::without_top_face`]
+    ///   by [`Self::without_top_face`], hexagonal columns are generated instead
     /// * This method will override or be overidden by
     ///   [`Self::with_cap_options`]
     pub const fn with_cap_uv_options(mut self, uv_options: UVOptions) -> Self {
@@ -203,7 +294,7 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
     ///
     /// Notes:
     /// * This won't have any effect if [`Self::top_face_options`] is disabled
-    ///   by [`Self::without_top_face`]
+    ///   by [`Self::without_top_face`], hexagonal columns are generated instead
     /// * This method will override or be overidden by
     ///   [`Self::with_cap_options`]
     #[must_use]
@@ -224,7 +315,7 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
     ///
     /// Notes:
     /// * This won't have any effect if [`Self::top_face_options`] is disabled
-    ///   by [`Self::without_top_face`]
+    ///   by [`Self::without_top_face`], hexagonal columns are generated instead
     #[must_use]
     #[inline]
     pub fn with_custom_cap_options(
@@ -325,20 +416,177 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
         self
     }
 
-    /// Comsumes the builder to return the computed mesh data
-    pub fn build(self) -> MeshInfo {
-        // We create the final mesh
-        let mut mesh = MeshInfo::default();
+    /// Computes the vertex tangents, with handedness, for normal mapped
+    /// materials (See [`MeshInfo::compute_tangents`])
+    #[must_use]
+    #[inline]
+    pub const fn with_tangents(mut self) -> Self {
+        self.tangents = true;
+        self
+    }
 
-        let [min, max] = match self.height_range {
-            Some(r) => [*r.start(), *r.end()],
-            None => [
-                self.map.values().copied().reduce(f32::min).unwrap_or(0.0),
-                self.map.values().copied().reduce(f32::max).unwrap_or(0.0),
-            ],
+    /// Specify per hexagon vertex colors and custom attribute values
+    #[must_use]
+    #[inline]
+    pub const fn with_vertex_attributes(mut self, attributes: &'l VertexAttributes) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+
+    /// Generates a smooth terrain instead of hexagonal columns.
+    ///
+    /// Each hexagon corner height is the average of the heights of the three
+    /// coordinates sharing it, and the hexagon surface slopes from its center
+    /// towards its corners.
+    ///
+    /// Notes:
+    /// * Missing neighbors are ignored in the averages, unless fringe heights
+    ///   are provided (See [`Self::with_fringe_heights`])
+    /// * The top face options are used for UV mapping, insetting is not
+    ///   supported
+    /// * No side quads are generated
+    /// * This won't have any effect if [`Self::top_face_options`] is disabled
+    ///   by [`Self::without_top_face`], hexagonal columns are generated instead
+    #[must_use]
+    #[inline]
+    pub const fn with_smoothing(mut self, options: SmoothOptions) -> Self {
+        self.smoothing = Some(options);
+        self
+    }
+
+    /// Computes the smooth terrain mesh, without transformations
+    fn smooth_mesh(&self, smoothing: SmoothOptions, cap_options: FaceOptions) -> MeshInfo {
+        let neighbor_height = |hex: Hex| {
+            self.map
+                .get(hex)
+                .copied()
+                .or_else(|| self.fringe_heights.as_ref().map(|f| f(hex)))
         };
+        let corners = self.layout.center_aligned_hex_corners();
+        let corner_uvs = corners.map(UVOptions::wrap_uv);
+        let steps = smoothing.subdivisions + 1;
+        #[expect(clippy::cast_precision_loss)]
+        let step = 1.0 / steps as f32;
+        // Triangle soup, with positions and uvs
+        let mut triangles: Vec<SmoothTriangle> = Vec::new();
+        for (hex, &height) in self.map.iter() {
+            let options = self
+                .custom_caps_options
+                .as_ref()
+                .and_then(|f| f(hex))
+                .unwrap_or(cap_options);
+            let center = self.layout.hex_to_center_aligned_world_pos(hex);
+            let corner_heights = VertexDirection::ALL_DIRECTIONS.map(|dir| {
+                let [sum, count] = [hex + dir.edge_ccw(), hex + dir.edge_cw()]
+                    .into_iter()
+                    .filter_map(neighbor_height)
+                    .fold([height, 1.0], |[sum, count], h| [sum + h, count + 1.0]);
+                sum / count
+            });
+            let first = triangles.len();
+            for i in 0..6 {
+                let j = (i + 1) % 6;
+                // Point of the `(center, i, j)` triangle with `a` steps towards
+                // corner `i` and `b` steps towards corner `j`
+                let point = |a: u32, b: u32| {
+                    #[expect(clippy::cast_precision_loss)]
+                    let [wa, wb] = [a as f32 * step, b as f32 * step];
+                    let radial = wa + wb;
+                    let edge_height = if radial > 0.0 {
+                        wa.mul_add(corner_heights[i], wb * corner_heights[j]) / radial
+                    } else {
+                        height
+                    };
+                    // Smoothstep transition from the center to the edge
+                    let t = radial * radial * 2.0_f32.mul_add(-radial, 3.0);
+                    let y = (edge_height - height).mul_add(t, height);
+                    let local = wa * corners[i] + wb * corners[j];
+                    let uv =
+                        Vec2::splat(0.5) * (1.0 - radial) + wa * corner_uvs[i] + wb * corner_uvs[j];
+                    (Vec3::new(center.x + local.x, y, center.y + local.y), uv)
+                };
+                for a in 0..steps {
+                    for b in 0..steps - a {
+                        let [p0, p1, p2] = [point(a, b), point(a + 1, b), point(a, b + 1)];
+                        triangles.push(([p0.0, p1.0, p2.0], [p0.1, p1.1, p2.1], hex));
+                        if a + b + 1 < steps {
+                            let p3 = point(a + 1, b + 1);
+                            triangles.push(([p1.0, p3.0, p2.0], [p1.1, p3.1, p2.1], hex));
+                        }
+                    }
+                }
+            }
+            for (_, uvs, _) in &mut triangles[first..] {
+                options.uv.alter_uvs(uvs);
+            }
+        }
+        self.triangles_mesh(triangles, smoothing.smooth_normals)
+    }
+
+    /// Builds an indexed mesh from a triangle soup, orienting every triangle
+    /// upwards
+    fn triangles_mesh(&self, triangles: Vec<SmoothTriangle>, smooth_normals: bool) -> MeshInfo {
+        let mut mesh = MeshInfo::default();
+        // Upwards facing normals of every triangle, weighted by area
+        let face_normals: Vec<Vec3> = triangles
+            .iter()
+            .map(|([a, b, c], _, _)| {
+                let normal = (*b - *a).cross(*c - *a);
+                if normal.y < 0.0 { -normal } else { normal }
+            })
+            .collect();
+        let snap = |p: Vec3| (p / SMOOTH_EPSILON).round().as_ivec3().to_array();
+        let mut shared_normals = HashMap::new();
+        if smooth_normals {
+            for ((positions, _, _), normal) in triangles.iter().zip(&face_normals) {
+                for p in positions {
+                    *shared_normals.entry(snap(*p)).or_insert(Vec3::ZERO) += *normal;
+                }
+            }
+        }
+        for ((positions, uvs, hex), normal) in triangles.into_iter().zip(face_normals) {
+            let [a, b, c] = positions;
+            // Triangles must face upwards
+            let order = if (b - a).cross(c - a).y < 0.0 {
+                [0, 2, 1]
+            } else {
+                [0, 1, 2]
+            };
+            for i in order {
+                let position = positions[i];
+                let normal = shared_normals
+                    .get(&snap(position))
+                    .copied()
+                    .unwrap_or(normal);
+                let index =
+                    u32::try_from(mesh.vertices.len()).expect("MeshInfo has too many vertices");
+                mesh.vertices.push(position);
+                mesh.normals.push(normal.normalize_or(Vec3::Y));
+                mesh.uvs.push(uvs[i]);
+                if let Some(attributes) = self.attributes {
+                    attributes.append(&mut mesh, hex, 1);
+                }
+                mesh.indices.push(index);
+            }
+        }
+        mesh.welded(SMOOTH_EPSILON)
+    }
+
+    /// Computes a column mesh for every hexagon of the map
+    fn columns_mesh(&self) -> MeshInfo {
+        let mut mesh = MeshInfo::default();
+        let [min, max] = self.height_range.as_ref().map_or_else(
+            || {
+                [
+                    self.map.values().copied().reduce(f32::min).unwrap_or(0.0),
+                    self.map.values().copied().reduce(f32::max).unwrap_or(0.0),
+                ]
+            },
+            |r| [*r.start(), *r.end()],
+        );
 
         for (hex, &height) in self.map.iter() {
+            let mut column = MeshInfo::default();
             if let Some(opts) = self.top_face_options {
                 // Maybe custom options
                 let opts = self
@@ -355,7 +603,7 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
                 if let Some(inset) = opts.insetting {
                     plane = plane.with_inset_options(inset);
                 }
-                mesh.merge_with(plane.build());
+                column.merge_with(plane.build());
             }
             if let Some(side_opts) = self.side_options {
                 let corners = self.layout.hex_edge_corners(hex);
@@ -379,10 +627,29 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
                         continue;
                     }
                     let quad = Quad::new_bounded([a, b], neighbor_height, height, [min, max]);
-                    mesh.merge_with(quad.apply_options(&side_opts));
+                    column.merge_with(quad.apply_options(&side_opts));
                 }
             }
+            if let Some(attributes) = self.attributes {
+                attributes.apply(&mut column, hex);
+            }
+            mesh.merge_with(column);
         }
+        mesh
+    }
+
+    /// Comsumes the builder to return the computed mesh data
+    #[must_use]
+    pub fn build(self) -> MeshInfo {
+        // Smoothing requires the top face
+        if let (Some(opts), Some(caps)) = (self.smoothing, self.top_face_options) {
+            return self.transformed(self.smooth_mesh(opts, caps));
+        }
+        self.transformed(self.columns_mesh())
+    }
+
+    /// Applies the builder transforms to the final `mesh`
+    fn transformed(&self, mut mesh: MeshInfo) -> MeshInfo {
         // **S** - We apply optional scale
         if let Some(scale) = self.scale {
             mesh = mesh.with_scale(scale);
@@ -398,6 +665,9 @@ impl<'l, 'm, HeightMap: HexStore<f32>> HeightMapMeshBuilder<'l, 'm, HeightMap> {
         if !self.center_aligned {
             mesh = mesh.with_offset(Vec3::new(self.layout.origin.x, 0.0, self.layout.origin.y));
         }
+        if self.tangents {
+            mesh.compute_tangents();
+        }
         mesh
     }
 }
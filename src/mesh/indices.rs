@@ -0,0 +1,151 @@
+/// Triangle vertex indices of a [`MeshInfo`](super::MeshInfo).
+///
+/// Indices are stored as `u16` as long as possible and are automatically
+/// promoted to `u32` when an index exceeds [`u16::MAX`], allowing large
+/// meshes without wasting memory on small ones.
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// let mut indices = MeshIndices::default();
+/// indices.extend([0, 1, 2]);
+/// assert!(matches!(indices, MeshIndices::U16(_)));
+/// indices.push(70_000);
+/// assert!(matches!(indices, MeshIndices::U32(_)));
+/// assert_eq!(indices.get(3), Some(70_000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum MeshIndices {
+    /// `u16` indices, for meshes with up to `65536` vertices
+    U16(Vec<u16>),
+    /// `u32` indices, for larger meshes
+    U32(Vec<u32>),
+}
+
+impl Default for MeshIndices {
+    fn default() -> Self {
+        Self::U16(Vec::new())
+    }
+}
+
+impl MeshIndices {
+    /// Returns the amount of indices
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::U16(v) => v.len(),
+            Self::U32(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if there are no indices
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index at position `i`, if any
+    #[inline]
+    #[must_use]
+    pub fn get(&self, i: usize) -> Option<u32> {
+        match self {
+            Self::U16(v) => v.get(i).copied().map(u32::from),
+            Self::U32(v) => v.get(i).copied(),
+        }
+    }
+
+    /// An iterator visiting all indices in order
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = u32> + '_ {
+        (0..self.len()).map(|i| match self {
+            Self::U16(v) => u32::from(v[i]),
+            Self::U32(v) => v[i],
+        })
+    }
+
+    /// Appends an index, promoting the storage to `u32` if needed
+    pub fn push(&mut self, index: u32) {
+        if let Self::U16(v) = self
+            && let Ok(i) = u16::try_from(index)
+        {
+            v.push(i);
+            return;
+        }
+        self.promote();
+        if let Self::U32(v) = self {
+            v.push(index);
+        }
+    }
+
+    /// Removes all indices, keeping the current storage type
+    pub fn clear(&mut self) {
+        match self {
+            Self::U16(v) => v.clear(),
+            Self::U32(v) => v.clear(),
+        }
+    }
+
+    /// Converts the storage to `u32` indices
+    pub fn promote(&mut self) {
+        if let Self::U16(v) = self {
+            *self = Self::U32(v.iter().copied().map(u32::from).collect());
+        }
+    }
+
+    /// Consumes the indices, returning them as `u32`
+    ///
+    /// This widens `u16` indices: match on the variant instead to keep the
+    /// smaller index buffer, as done by the `bevy_mesh` conversion.
+    #[must_use]
+    pub fn into_u32(self) -> Vec<u32> {
+        match self {
+            Self::U16(v) => v.into_iter().map(u32::from).collect(),
+            Self::U32(v) => v,
+        }
+    }
+
+    /// Consumes the indices, returning them as `u16` or `None` if any index
+    /// exceeds [`u16::MAX`]
+    #[must_use]
+    pub fn into_u16(self) -> Option<Vec<u16>> {
+        match self {
+            Self::U16(v) => Some(v),
+            Self::U32(v) => v.into_iter().map(|i| u16::try_from(i).ok()).collect(),
+        }
+    }
+}
+
+impl Extend<u32> for MeshIndices {
+    fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+        for index in iter {
+            self.push(index);
+        }
+    }
+}
+
+impl FromIterator<u32> for MeshIndices {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut res = Self::default();
+        res.extend(iter);
+        res
+    }
+}
+
+impl From<Vec<u16>> for MeshIndices {
+    #[inline]
+    fn from(value: Vec<u16>) -> Self {
+        Self::U16(value)
+    }
+}
+
+impl From<Vec<u32>> for MeshIndices {
+    #[inline]
+    fn from(value: Vec<u32>) -> Self {
+        Self::U32(value)
+    }
+}
@@ -0,0 +1,411 @@
+use super::{BASE_FACING, MeshInfo, face::Quad};
+use crate::{BoundaryLoop, EdgeDirection, Hex, HexLayout, UVOptions, storage::HexStore};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+use glam::{Quat, Vec2, Vec3};
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+
+/// Aggregation of the heights of the coordinates merged in a single hexagon
+/// by [`LodMeshBuilder`]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum HeightAggregation {
+    /// Average height
+    #[default]
+    Mean,
+    /// Lowest height
+    Min,
+    /// Highest height
+    Max,
+}
+
+impl HeightAggregation {
+    /// Aggregates the given `heights`, returns `None` if empty
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn aggregate(self, heights: impl IntoIterator<Item = f32>) -> Option<f32> {
+        let mut iter = heights.into_iter();
+        let first = iter.next()?;
+        let res = match self {
+            Self::Mean => {
+                let (sum, count) = iter.fold((first, 1), |(sum, count), h| (sum + h, count + 1));
+                sum / count as f32
+            }
+            Self::Min => iter.fold(first, f32::min),
+            Self::Max => iter.fold(first, f32::max),
+        };
+        Some(res)
+    }
+}
+
+/// Builder struct to generate level of detail (LOD) terrain meshes of
+/// hexagonal chunks.
+///
+/// The chunk is the hexagonal area of radius `chunk_radius` around
+/// `chunk.to_higher_res(chunk_radius)`, matching [`Hex::to_lower_res`]. Its
+/// coordinates are grouped by *super hexagons* of radius [`Self::lod`]
+/// (See [`Hex::to_lower_res`]) and every group is rendered as a single flat
+/// polygon at the aggregated height of its coordinates.
+///
+/// * A `lod` of `0` generates one hexagon per coordinate, like
+///   [`HeightMapMeshBuilder`](crate::HeightMapMeshBuilder)
+/// * Higher `lod` values generate coarser meshes, for distant chunks
+///
+/// Whatever the `lod`, the outline of the chunk remains the same, so chunks of
+/// different levels fit together. The difference of heights between chunks
+/// of different levels is hidden by vertical *skirts* hanging from the chunk
+/// outline (See [`Self::with_skirt_depth`]).
+///
+/// # UV mapping
+///
+/// The top faces are mapped on the chunk bounding rectangle, so that textures
+/// match across levels. Side quads and skirts use the default quad uvs.
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// # use std::collections::HashMap;
+/// const CHUNK_RADIUS: u32 = 8;
+///
+/// let layout = HexLayout::default();
+/// let map: HashMap<Hex, f32> = Hex::ZERO.range(30).map(|h| (h, h.x as f32)).collect();
+/// let chunk = hex(1, 0);
+/// let full = LodMeshBuilder::new(&layout, &map, chunk, CHUNK_RADIUS).build();
+/// let coarse = LodMeshBuilder::new(&layout, &map, chunk, CHUNK_RADIUS)
+///     .with_lod(2)
+///     .with_aggregation(HeightAggregation::Max)
+///     .build();
+/// assert!(coarse.indices.len() < full.indices.len());
+/// ```
+///
+/// # Notes
+///
+/// Transform operations (Scale, Rotate, Translate) through the methods
+///
+/// - Scale: [`Self::with_scale`]
+/// - Rotate: [`Self::with_rotation`], [`Self::facing`]
+/// - Translate: [`Self::with_offset`]
+///
+/// Are executed in that order, or **SRT**
+#[derive(Debug, Clone)]
+pub struct LodMeshBuilder<'l, 'm, HeightMap> {
+    /// The hexagonal layout, used to compute vertex positions
+    pub layout: &'l HexLayout,
+    /// Map between the coordinates and the associated height
+    pub map: &'m HeightMap,
+    /// The lower resolution chunk coordinate
+    pub chunk: Hex,
+    /// The chunk radius, as used in [`Hex::to_lower_res`]
+    pub chunk_radius: u32,
+    /// The level of detail, the radius of the merged super hexagons. `0` means
+    /// full resolution
+    pub lod: u32,
+    /// Height aggregation mode of the merged coordinates
+    pub aggregation: HeightAggregation,
+    /// Depth of the skirts hanging from the chunk outline. If `None` no skirt
+    /// is generated
+    pub skirt_depth: Option<f32>,
+    /// If set to `true`, vertical side quads are generated between groups of
+    /// different heights
+    pub sides: bool,
+    /// UV mapping options
+    pub uv_options: UVOptions,
+    /// Optional custom offset for the mesh vertex positions
+    pub offset: Option<Vec3>,
+    /// Optional custom scale factor for the mesh vertex positions
+    pub scale: Option<Vec3>,
+    /// Optional custom rotation, useful to have the mesh already
+    /// rotated
+    ///
+    /// By default the mesh is *facing* up (**Y** axis)
+    pub rotation: Option<Quat>,
+    /// If set to `true`, the mesh will ignore [`HexLayout::origin`]
+    pub center_aligned: bool,
+}
+
+impl<'l, 'm, HeightMap: HexStore<f32>> LodMeshBuilder<'l, 'm, HeightMap> {
+    /// Setup a new builder using the given `layout` and height `map` for the
+    /// lower resolution `chunk` of radius `chunk_radius`, at full resolution
+    #[must_use]
+    pub const fn new(
+        layout: &'l HexLayout,
+        map: &'m HeightMap,
+        chunk: Hex,
+        chunk_radius: u32,
+    ) -> Self {
+        Self {
+            layout,
+            map,
+            chunk,
+            chunk_radius,
+            lod: 0,
+            aggregation: HeightAggregation::Mean,
+            skirt_depth: Some(1.0),
+            sides: true,
+            uv_options: UVOptions::new(),
+            offset: None,
+            scale: None,
+            rotation: None,
+            center_aligned: false,
+        }
+    }
+
+    /// Specify the level of detail, the radius of the super hexagons merging
+    /// the chunk coordinates. (Default is `0`, full resolution)
+    #[must_use]
+    pub const fn with_lod(mut self, lod: u32) -> Self {
+        self.lod = lod;
+        self
+    }
+
+    /// Specify the height aggregation mode of merged coordinates
+    #[must_use]
+    pub const fn with_aggregation(mut self, aggregation: HeightAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Specify the depth of the skirts hanging from the chunk outline
+    /// (Default is `1.0`)
+    #[must_use]
+    pub const fn with_skirt_depth(mut self, depth: f32) -> Self {
+        self.skirt_depth = Some(depth);
+        self
+    }
+
+    /// Disables the skirts
+    #[must_use]
+    pub const fn without_skirts(mut self) -> Self {
+        self.skirt_depth = None;
+        self
+    }
+
+    /// Disables the vertical side quads between groups
+    #[must_use]
+    pub const fn without_sides(mut self) -> Self {
+        self.sides = false;
+        self
+    }
+
+    /// Specify custom UV mapping options for the top faces
+    #[must_use]
+    pub const fn with_uv_options(mut self, uv_options: UVOptions) -> Self {
+        self.uv_options = uv_options;
+        self
+    }
+
+    /// Specify a custom *facing* direction for the mesh, by default the mesh
+    /// is horizontal (facing up)
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `facing` is zero length
+    #[must_use]
+    pub fn facing(mut self, facing: Vec3) -> Self {
+        self.rotation = Some(Quat::from_rotation_arc(BASE_FACING, facing.normalize()));
+        self
+    }
+
+    /// Specify a custom rotation for the whole mesh
+    #[must_use]
+    pub const fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    /// Specify a custom offset for the whole mesh
+    #[must_use]
+    pub const fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Specify a custom scale factor for the whole mesh
+    #[must_use]
+    pub const fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    #[must_use]
+    #[inline]
+    /// Ignores the [`HexLayout::origin`] offset, generating a mesh centered
+    /// around `(0.0, 0.0)`.
+    pub const fn center_aligned(mut self) -> Self {
+        self.center_aligned = true;
+        self
+    }
+
+    /// Returns the super hexagon of `hex` at the current level of detail
+    fn group_of(&self, hex: Hex) -> Hex {
+        if self.lod == 0 {
+            hex
+        } else {
+            hex.to_lower_res(self.lod)
+        }
+    }
+
+    /// Computes the flat top polygons of a group at `height`
+    fn group_mesh(&self, coords: &[Hex], height: f32, [min, max]: [Vec2; 2]) -> MeshInfo {
+        let loops = BoundaryLoop::from_region(coords.iter().copied());
+        // Groups split by missing coordinates fall back to individual hexagons
+        let polygons = if loops.len() == 1 {
+            vec![self.layout.boundary_coordinates(&loops[0])]
+        } else {
+            coords
+                .iter()
+                .map(|h| self.layout.hex_corners(*h).to_vec())
+                .collect()
+        };
+        let mut mesh = MeshInfo::default();
+        for points in polygons {
+            let offset =
+                u32::try_from(mesh.vertices.len()).expect("MeshInfo has too many vertices");
+            for p in &points {
+                mesh.vertices.push(Vec3::new(p.x, height, p.y));
+                mesh.normals.push(BASE_FACING);
+                mesh.uvs.push((*p - min) / (max - min));
+            }
+            for [a, b, c] in triangulate(&points) {
+                // Triangles must face upwards
+                let tri = if (points[b] - points[a]).perp_dot(points[c] - points[a]) > 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                };
+                mesh.indices.extend(
+                    tri.map(|i| offset + u32::try_from(i).expect("MeshInfo has too many vertices")),
+                );
+            }
+        }
+        mesh
+    }
+
+    /// Comsumes the builder to return the computed mesh data
+    #[must_use]
+    pub fn build(self) -> MeshInfo {
+        let center = self.chunk.to_higher_res(self.chunk_radius);
+        let mut group_ids = HashMap::new();
+        let mut groups: Vec<(Vec<Hex>, Vec<f32>)> = Vec::new();
+        let (mut min, mut max) = (Vec2::MAX, Vec2::MIN);
+        for hex in center.range(self.chunk_radius) {
+            let Some(height) = self.map.get(hex).copied() else {
+                continue;
+            };
+            let id = *group_ids.entry(self.group_of(hex)).or_insert_with(|| {
+                groups.push((Vec::new(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[id].0.push(hex);
+            groups[id].1.push(height);
+            for corner in self.layout.hex_corners(hex) {
+                min = min.min(corner);
+                max = max.max(corner);
+            }
+        }
+        let mut heights = HashMap::new();
+        let mut mesh = MeshInfo::default();
+        let [mut min_height, mut max_height] = [f32::MAX, f32::MIN];
+        for (coords, values) in &groups {
+            // Groups are never empty
+            let height = self
+                .aggregation
+                .aggregate(values.iter().copied())
+                .unwrap_or_default();
+            min_height = min_height.min(height);
+            max_height = max_height.max(height);
+            heights.extend(coords.iter().map(|h| (*h, height)));
+            mesh.merge_with(self.group_mesh(coords, height, [min, max]));
+        }
+        self.uv_options.alter_uvs(&mut mesh.uvs);
+        let corners = self.layout.center_aligned_edge_corners();
+        for hex in groups.iter().flat_map(|(coords, _)| coords) {
+            let height = &heights[hex];
+            let pos = self.layout.hex_to_world_pos(*hex);
+            let pos = Vec3::new(pos.x, 0.0, pos.y);
+            for dir in EdgeDirection::ALL_DIRECTIONS {
+                let sides = corners[dir.index() as usize];
+                let quad = match heights.get(&(*hex + dir)) {
+                    Some(neighbor) if self.sides && neighbor < height => {
+                        Quad::new_bounded(sides, *neighbor, *height, [min_height, max_height])
+                    }
+                    None => match self.skirt_depth {
+                        Some(depth) => Quad::new(sides, *height - depth, *height),
+                        None => continue,
+                    },
+                    Some(_) => continue,
+                };
+                mesh.merge_with(MeshInfo::from(quad).with_offset(pos));
+            }
+        }
+        if self.center_aligned {
+            let origin = self.layout.origin;
+            mesh = mesh.with_offset(Vec3::new(-origin.x, 0.0, -origin.y));
+        }
+        // **S** - We apply optional scale
+        if let Some(scale) = self.scale {
+            mesh = mesh.with_scale(scale);
+        }
+        // **R** - We rotate the mesh to face the given direction
+        if let Some(rotation) = self.rotation {
+            mesh = mesh.rotated(rotation);
+        }
+        // **T** - We offset the vertex positions after scaling and rotating
+        if let Some(offset) = self.offset {
+            mesh = mesh.with_offset(offset);
+        }
+        mesh
+    }
+}
+
+/// Triangulates a simple polygon by ear clipping, returning triangles of
+/// indices in `points`
+fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    // Orientation of the polygon, so that ears are convex corners
+    let orientation = (0..points.len())
+        .map(|i| points[i].perp_dot(points[(i + 1) % points.len()]))
+        .sum::<f32>()
+        .signum();
+    let cross = |a: Vec2, b: Vec2, c: Vec2| (b - a).perp_dot(c - a) * orientation;
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let ear = (0..len).find(|&i| {
+            let [a, b, c] = [
+                remaining[(i + len - 1) % len],
+                remaining[i],
+                remaining[(i + 1) % len],
+            ];
+            let [pa, pb, pc] = [points[a], points[b], points[c]];
+            cross(pa, pb, pc) > 0.0
+                && remaining
+                    .iter()
+                    .filter(|j| ![a, b, c].contains(j))
+                    .all(|&j| {
+                        let p = points[j];
+                        cross(pa, pb, p) < 0.0 || cross(pb, pc, p) < 0.0 || cross(pc, pa, p) < 0.0
+                    })
+        });
+        // Degenerate polygons are fanned from the first remaining point
+        let Some(i) = ear else {
+            break;
+        };
+        triangles.push([
+            remaining[(i + len - 1) % len],
+            remaining[i],
+            remaining[(i + 1) % len],
+        ]);
+        remaining.remove(i);
+    }
+    triangles.extend(
+        remaining
+            .windows(2)
+            .skip(1)
+            .map(|w| [remaining[0], w[0], w[1]]),
+    );
+    triangles
+}
@@ -1,18 +1,36 @@
+mod attributes;
+#[cfg(feature = "bevy_mesh")]
+mod bevy;
+#[cfg(feature = "grid")]
+pub(crate) mod border_builder;
 pub(crate) mod column_builder;
+#[cfg(feature = "mesh_export")]
+mod export;
 /// Utility module for mesh construction
 pub mod face;
 pub(crate) mod heightmap_builder;
+mod indices;
+#[cfg(feature = "grid")]
+pub(crate) mod lod_builder;
+mod optimize;
 pub(crate) mod plane_builder;
+mod tangents;
 #[cfg(test)]
 mod tests;
 mod uv_mapping;
 
+pub use attributes::{CustomAttribute, VertexAttributes};
+#[cfg(feature = "grid")]
+pub use border_builder::BorderMeshBuilder;
 pub use column_builder::ColumnMeshBuilder;
-pub use heightmap_builder::HeightMapMeshBuilder;
+pub use heightmap_builder::{HeightMapMeshBuilder, SmoothOptions};
+pub use indices::MeshIndices;
+#[cfg(feature = "grid")]
+pub use lod_builder::{HeightAggregation, LodMeshBuilder};
 pub use plane_builder::PlaneMeshBuilder;
 pub use uv_mapping::{Rect, UVOptions};
 
-use glam::{Quat, Vec2, Vec3};
+use glam::{Quat, Vec2, Vec3, Vec4};
 
 use crate::{Hex, HexLayout};
 
@@ -110,12 +128,30 @@ pub struct MeshInfo {
     pub normals: Vec<Vec3>,
     /// UV coordinates of each vertex (`Vertex_Uv` attribute)
     pub uvs: Vec<Vec2>,
+    /// Tangents for each vertex, with the handedness stored in `w`
+    /// (`Vertex_Tangent` attribute).
+    ///
+    /// Empty unless computed, see [`Self::compute_tangents`] or the
+    /// `with_tangents` option of the mesh builders
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tangents: Vec<Vec4>,
+    /// Linear RGBA colors for each vertex (`Vertex_Color` attribute).
+    ///
+    /// Empty unless specified, see [`VertexAttributes`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colors: Vec<Vec4>,
+    /// Optional extra channel with a value for each vertex.
+    ///
+    /// See [`VertexAttributes`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub custom: Option<CustomAttribute>,
     /// Vertex indices for triangles
-    pub indices: Vec<u16>,
+    pub indices: MeshIndices,
 }
 
 impl MeshInfo {
-    /// Returns a new [`MeshInfo`] but with vertex positions and normals rotated
+    /// Returns a new [`MeshInfo`] but with vertex positions, normals and
+    /// tangents rotated
     #[inline]
     #[must_use]
     pub fn rotated(mut self, rotation: Quat) -> Self {
@@ -125,6 +161,9 @@ impl MeshInfo {
         self.normals
             .iter_mut()
             .for_each(|n| *n = rotation.mul_vec3(*n));
+        self.tangents
+            .iter_mut()
+            .for_each(|t| *t = rotation.mul_vec3(t.truncate()).extend(t.w));
         self
     }
 
@@ -136,11 +175,18 @@ impl MeshInfo {
         self
     }
 
-    /// Returns a new [`MeshInfo`] but with `scale` applied to vertex positions
+    /// Returns a new [`MeshInfo`] but with `scale` applied to vertex positions.
+    ///
+    /// Normals and tangents are updated to match the scaled surface
     #[inline]
     #[must_use]
     pub fn with_scale(mut self, scale: Vec3) -> Self {
         self.vertices.iter_mut().for_each(|p| *p *= scale);
+        let inverse = scale.recip();
+        self.normals
+            .iter_mut()
+            .for_each(|n| *n = (*n * inverse).normalize_or(*n));
+        self.scale_tangents(scale);
         self
     }
 
@@ -149,6 +195,7 @@ impl MeshInfo {
     #[must_use]
     pub fn with_uv_scale(mut self, scale: Vec2) -> Self {
         self.uvs.iter_mut().for_each(|c| *c *= scale);
+        self.uv_scale_tangents(scale);
         self
     }
 
@@ -176,19 +223,38 @@ impl MeshInfo {
     ///
     /// # Note
     ///
-    /// This method doesn't merge vertices or tries to remove invisible faces.
+    /// This method doesn't merge vertices or tries to remove invisible faces,
+    /// use [`Self::optimized`] after merging for that purpose.
+    ///
+    /// The indices are promoted to `u32` if needed (See [`MeshIndices`])
+    ///
+    /// If only one of the meshes has tangents, they are computed for the
+    /// other one (See [`Self::compute_tangents`]). Likewise missing vertex
+    /// colors are white and missing [`Self::custom`] values are zeros.
     ///
     /// # Panics
     ///
-    /// Will panic if there are more vertices than [`u16::MAX`]
-    pub fn merge_with(&mut self, rhs: Self) {
+    /// Will panic if there are more vertices than [`u32::MAX`] or if the
+    /// meshes have different [`CustomAttribute`] types
+    pub fn merge_with(&mut self, mut rhs: Self) {
         let indices_offset =
-            u16::try_from(self.vertices.len()).expect("MeshInfo has too many vertices");
+            u32::try_from(self.vertices.len()).expect("MeshInfo has too many vertices");
+        if self.has_tangents() && !rhs.vertices.is_empty() && !rhs.has_tangents() {
+            rhs.compute_tangents();
+        } else if rhs.has_tangents() && !self.vertices.is_empty() && !self.has_tangents() {
+            self.compute_tangents();
+        }
+        self.pad_attributes(&mut rhs);
         self.vertices.extend(rhs.vertices);
         self.normals.extend(rhs.normals);
         self.uvs.extend(rhs.uvs);
+        self.tangents.extend(rhs.tangents);
+        self.colors.extend(rhs.colors);
+        if let (Some(custom), Some(other)) = (&mut self.custom, rhs.custom) {
+            custom.append(other);
+        }
         self.indices
-            .extend(rhs.indices.into_iter().map(|i| i + indices_offset));
+            .extend(rhs.indices.iter().map(|i| i + indices_offset));
     }
 
     /// Computes cheap mesh data for an hexagonal column facing `Vec3::Y`
@@ -238,7 +304,7 @@ impl MeshInfo {
             bot_corners[4], // 10
             bot_corners[5], // 11
         ];
-        let indices = vec![
+        let indices = MeshIndices::U16(vec![
             // Top Face triangles
             0, 2, 1, // 0
             3, 5, 4, // 1
@@ -251,7 +317,7 @@ impl MeshInfo {
             3, 4, 10, 10, 9, 3, // Quad 3
             4, 5, 11, 11, 10, 4, // Quad 4
             5, 0, 6, 6, 11, 5, // Quad 5
-        ];
+        ]);
         Self {
             vertices,
             normals: vec![
@@ -273,6 +339,9 @@ impl MeshInfo {
                 uvs[4], uvs[5],
             ]
             .to_vec(),
+            tangents: Vec::new(),
+            colors: Vec::new(),
+            custom: None,
             indices,
         }
     }
@@ -0,0 +1,230 @@
+use super::MeshInfo;
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+use glam::{Vec2, Vec3, Vec4};
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+
+/// Tolerance used by [`MeshInfo::optimized`]
+const DEFAULT_EPSILON: f32 = 1e-4;
+/// Minimum normal alignment for two faces to be considered coplanar
+const FACING_THRESHOLD: f32 = 1.0 - 1e-3;
+
+type PosKey = [i32; 3];
+
+impl MeshInfo {
+    /// Returns a new [`MeshInfo`] with coincident vertices welded together
+    /// and unused vertices removed.
+    ///
+    /// Two vertices are welded if all their attributes (positions, normals,
+    /// uvs, tangents, colors and custom values) are equal once snapped to a
+    /// grid of size `epsilon`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `epsilon` is not strictly positive
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let layout = HexLayout::default();
+    /// let mut mesh = PlaneMeshBuilder::new(&layout).build();
+    /// mesh.merge_with(PlaneMeshBuilder::new(&layout).build());
+    /// assert_eq!(mesh.vertices.len(), 12);
+    /// let mesh = mesh.welded(1e-4);
+    /// assert_eq!(mesh.vertices.len(), 6);
+    /// assert_eq!(mesh.indices.len(), 24);
+    /// ```
+    #[must_use]
+    pub fn welded(self, epsilon: f32) -> Self {
+        assert!(epsilon > 0.0, "Welding epsilon must be strictly positive");
+        let snap3 = |v: Vec3| (v / epsilon).round().as_ivec3().to_array();
+        let snap2 = |v: Vec2| (v / epsilon).round().as_ivec2().to_array();
+        let snap4 = |v: Vec4| (v / epsilon).round().as_ivec4().to_array();
+        let mut welded = HashMap::new();
+        let mut res = Self::default();
+        let indices: Vec<u32> = self
+            .indices
+            .iter()
+            .map(|i| {
+                let i = i as usize;
+                let key = (
+                    snap3(self.vertices[i]),
+                    snap3(self.normals[i]),
+                    snap2(self.uvs[i]),
+                    self.tangents.get(i).copied().map(snap4),
+                    self.colors.get(i).copied().map(snap4),
+                    self.custom.as_ref().map(|c| c.snapped(i, epsilon)),
+                );
+                *welded
+                    .entry(key)
+                    .or_insert_with(|| res.push_vertex(&self, i))
+            })
+            .collect();
+        res.indices = indices.into_iter().collect();
+        res
+    }
+
+    /// Returns a new [`MeshInfo`] without the hidden faces, and with unused
+    /// vertices removed.
+    ///
+    /// A face is hidden if another face covers the exact same area facing the
+    /// opposite direction, like the side quads between two neighboring
+    /// columns of equal height in a merged mesh. Both faces are removed.
+    ///
+    /// Faces are either single triangles or quads made of two coplanar
+    /// triangles, positions are compared on a grid of size `epsilon`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `epsilon` is not strictly positive
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let layout = HexLayout::default();
+    /// let column = |hex| ColumnMeshBuilder::new(&layout, 1.0).at(hex).build();
+    /// let mut mesh = column(Hex::ZERO);
+    /// mesh.merge_with(column(hex(1, 0)));
+    /// let culled = mesh.clone().without_hidden_faces(1e-4);
+    /// // The two side quads between the columns are removed
+    /// assert_eq!(culled.indices.len(), mesh.indices.len() - 2 * 6);
+    /// assert_eq!(culled.vertices.len(), mesh.vertices.len() - 2 * 4);
+    /// ```
+    #[must_use]
+    pub fn without_hidden_faces(self, epsilon: f32) -> Self {
+        assert!(epsilon > 0.0, "Culling epsilon must be strictly positive");
+        let snap = |i: u32| {
+            (self.vertices[i as usize] / epsilon)
+                .round()
+                .as_ivec3()
+                .to_array()
+        };
+        let indices: Vec<u32> = self.indices.iter().collect();
+        let triangles: Vec<[u32; 3]> = indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+        let facings: Vec<Vec3> = triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| self.vertices[i as usize]);
+                (b - a).cross(c - a).normalize_or_zero()
+            })
+            .collect();
+        // Candidate faces, keyed by their sorted corner positions
+        let mut face_ids: HashMap<Vec<PosKey>, usize> = HashMap::new();
+        let mut faces: Vec<Vec<(Vec<usize>, Vec3)>> = Vec::new();
+        let mut add_face = |mut corners: Vec<PosKey>, members: Vec<usize>, facing: Vec3| {
+            corners.sort_unstable();
+            let id = *face_ids.entry(corners).or_insert_with(|| {
+                faces.push(Vec::new());
+                faces.len() - 1
+            });
+            faces[id].push((members, facing));
+        };
+        let mut edges: HashMap<[PosKey; 2], Vec<usize>> = HashMap::new();
+        let mut edge_order = Vec::new();
+        for (t, (triangle, facing)) in triangles.iter().zip(&facings).enumerate() {
+            if *facing == Vec3::ZERO {
+                continue;
+            }
+            let corners = triangle.map(snap);
+            add_face(corners.to_vec(), vec![t], *facing);
+            for [a, b] in [[0, 1], [1, 2], [2, 0]] {
+                let mut edge = [corners[a], corners[b]];
+                edge.sort_unstable();
+                let tris = edges.entry(edge).or_insert_with(|| {
+                    edge_order.push(edge);
+                    Vec::new()
+                });
+                tris.push(t);
+            }
+        }
+        // Quads made of two coplanar triangles sharing an edge
+        for edge in edge_order {
+            let tris = &edges[&edge];
+            for (i, &a) in tris.iter().enumerate() {
+                for &b in &tris[i + 1..] {
+                    if facings[a].dot(facings[b]) < FACING_THRESHOLD {
+                        continue;
+                    }
+                    let mut corners: Vec<PosKey> = triangles[a]
+                        .iter()
+                        .chain(&triangles[b])
+                        .map(|i| snap(*i))
+                        .collect();
+                    corners.sort_unstable();
+                    corners.dedup();
+                    if corners.len() == 4 {
+                        add_face(corners, vec![a, b], facings[a]);
+                    }
+                }
+            }
+        }
+        // Removes faces covering the same area with opposite facings
+        let mut removed = vec![false; triangles.len()];
+        for candidates in faces {
+            for (i, (members_a, facing_a)) in candidates.iter().enumerate() {
+                for (members_b, facing_b) in &candidates[i + 1..] {
+                    let available = members_a.iter().chain(members_b).all(|t| !removed[*t]);
+                    if available && facing_a.dot(*facing_b) < -FACING_THRESHOLD {
+                        for t in members_a.iter().chain(members_b) {
+                            removed[*t] = true;
+                        }
+                    }
+                }
+            }
+        }
+        let mut res = Self::default();
+        let mut remap = HashMap::new();
+        let indices: Vec<u32> = triangles
+            .iter()
+            .zip(removed)
+            .filter(|(_, removed)| !removed)
+            .flat_map(|(t, _)| *t)
+            .map(|i| {
+                *remap
+                    .entry(i)
+                    .or_insert_with(|| res.push_vertex(&self, i as usize))
+            })
+            .collect();
+        res.indices = indices.into_iter().collect();
+        res
+    }
+
+    /// Returns a new optimized [`MeshInfo`], with hidden faces removed and
+    /// coincident vertices welded.
+    ///
+    /// This is useful after merging many meshes together with
+    /// [`Self::merge_with`], for example neighboring columns.
+    ///
+    /// > See [`Self::without_hidden_faces`] and [`Self::welded`]
+    #[must_use]
+    pub fn optimized(self) -> Self {
+        self.without_hidden_faces(DEFAULT_EPSILON)
+            .welded(DEFAULT_EPSILON)
+    }
+
+    /// Copies the vertex at `index` in `source`, returning its new index
+    fn push_vertex(&mut self, source: &Self, index: usize) -> u32 {
+        let new_index = u32::try_from(self.vertices.len()).expect("MeshInfo has too many vertices");
+        self.vertices.push(source.vertices[index]);
+        self.normals.push(source.normals[index]);
+        self.uvs.push(source.uvs[index]);
+        if let Some(tangent) = source.tangents.get(index) {
+            self.tangents.push(*tangent);
+        }
+        if let Some(color) = source.colors.get(index) {
+            self.colors.push(*color);
+        }
+        if let Some(custom) = &source.custom {
+            self.custom
+                .get_or_insert_with(|| custom.empty_like())
+                .push_from(custom, index);
+        }
+        new_index
+    }
+}
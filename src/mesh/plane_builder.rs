@@ -1,4 +1,6 @@
-use crate::{BASE_FACING, Hex, HexLayout, InsetOptions, MeshInfo, UVOptions, face::Hexagon};
+use crate::{
+    BASE_FACING, Hex, HexLayout, InsetOptions, MeshInfo, UVOptions, VertexAttributes, face::Hexagon,
+};
 use glam::{Quat, Vec3};
 
 use super::FaceOptions;
@@ -36,6 +38,10 @@ pub struct PlaneMeshBuilder<'l> {
     pub face_options: FaceOptions,
     /// If set to `true`, the mesh will ignore [`HexLayout::origin`]
     pub center_aligned: bool,
+    /// If set to `true`, vertex tangents are computed for normal mapping
+    pub tangents: bool,
+    /// Per hexagon vertex colors and custom attribute
+    pub attributes: Option<&'l VertexAttributes>,
 }
 
 impl<'l> PlaneMeshBuilder<'l> {
@@ -50,6 +56,8 @@ impl<'l> PlaneMeshBuilder<'l> {
             scale: None,
             face_options: FaceOptions::new(),
             center_aligned: false,
+            tangents: false,
+            attributes: None,
         }
     }
 
@@ -129,6 +137,23 @@ impl<'l> PlaneMeshBuilder<'l> {
         self
     }
 
+    /// Computes the vertex tangents, with handedness, for normal mapped
+    /// materials (See [`MeshInfo::compute_tangents`])
+    #[must_use]
+    #[inline]
+    pub const fn with_tangents(mut self) -> Self {
+        self.tangents = true;
+        self
+    }
+
+    /// Specify per hexagon vertex colors and custom attribute values
+    #[must_use]
+    #[inline]
+    pub const fn with_vertex_attributes(mut self, attributes: &'l VertexAttributes) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+
     /// Comsumes the builder to return the computed mesh data
     #[must_use]
     pub fn build(self) -> MeshInfo {
@@ -161,6 +186,12 @@ impl<'l> PlaneMeshBuilder<'l> {
         }
         mesh = mesh.with_offset(offset);
         self.face_options.uv.alter_uvs(&mut mesh.uvs);
+        if let Some(attributes) = self.attributes {
+            attributes.apply(&mut mesh, self.pos);
+        }
+        if self.tangents {
+            mesh.compute_tangents();
+        }
         mesh
     }
 }
@@ -0,0 +1,107 @@
+use super::MeshInfo;
+use glam::{Vec2, Vec3};
+
+impl MeshInfo {
+    /// Computes the [`Self::tangents`] of every vertex from the positions,
+    /// normals and uvs of the mesh triangles.
+    ///
+    /// Tangents follow the `u` direction of the uvs and are orthogonal to the
+    /// vertex normals. The `w` component stores the handedness (`1.0` or
+    /// `-1.0`) so that the bitangent is `normal.cross(tangent.xyz()) * w`.
+    ///
+    /// Vertices without a valid uv gradient get an arbitrary tangent
+    /// orthogonal to their normal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::*;
+    /// let layout = HexLayout::default();
+    /// let mut mesh = ColumnMeshBuilder::new(&layout, 1.0).build();
+    /// assert!(mesh.tangents.is_empty());
+    /// mesh.compute_tangents();
+    /// assert_eq!(mesh.tangents.len(), mesh.vertices.len());
+    /// ```
+    pub fn compute_tangents(&mut self) {
+        let len = self.vertices.len();
+        let mut tangents = vec![Vec3::ZERO; len];
+        let mut bitangents = vec![Vec3::ZERO; len];
+        let indices: Vec<usize> = self.indices.iter().map(|i| i as usize).collect();
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+            let [e1, e2] = [
+                self.vertices[b] - self.vertices[a],
+                self.vertices[c] - self.vertices[a],
+            ];
+            let [d1, d2] = [self.uvs[b] - self.uvs[a], self.uvs[c] - self.uvs[a]];
+            let det = d1.perp_dot(d2);
+            if det.abs() <= f32::EPSILON {
+                continue;
+            }
+            // Area weighted gradients of `u` and `v`
+            let sign = det.signum();
+            let tangent = (e1 * d2.y - e2 * d1.y) * sign;
+            let bitangent = (e2 * d1.x - e1 * d2.x) * sign;
+            for i in [a, b, c] {
+                tangents[i] += tangent;
+                bitangents[i] += bitangent;
+            }
+        }
+        self.tangents = self
+            .normals
+            .iter()
+            .zip(tangents.into_iter().zip(bitangents))
+            .map(|(normal, (tangent, bitangent))| {
+                // Gram-Schmidt orthogonalization
+                let tangent = (tangent - *normal * normal.dot(tangent))
+                    .try_normalize()
+                    .unwrap_or_else(|| normal.any_orthonormal_vector());
+                let handedness = if normal.cross(tangent).dot(bitangent) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                tangent.extend(handedness)
+            })
+            .collect();
+    }
+
+    /// Returns a new [`MeshInfo`] with computed tangents.
+    ///
+    /// > See [`Self::compute_tangents`]
+    #[inline]
+    #[must_use]
+    pub fn with_tangents(mut self) -> Self {
+        self.compute_tangents();
+        self
+    }
+
+    /// Returns `true` if [`Self::tangents`] are computed for every vertex
+    #[inline]
+    #[must_use]
+    pub const fn has_tangents(&self) -> bool {
+        self.tangents.len() == self.vertices.len() && !self.tangents.is_empty()
+    }
+
+    /// Applies a vertex position `scale` to the tangents
+    pub(super) fn scale_tangents(&mut self, scale: Vec3) {
+        let flip = scale.x * scale.y * scale.z < 0.0;
+        for t in &mut self.tangents {
+            let tangent = (t.truncate() * scale).normalize_or(t.truncate());
+            *t = tangent.extend(if flip { -t.w } else { t.w });
+        }
+    }
+
+    /// Applies a uv `scale` to the tangents
+    pub(super) fn uv_scale_tangents(&mut self, scale: Vec2) {
+        let flip = scale.x * scale.y < 0.0;
+        for t in &mut self.tangents {
+            let tangent = if scale.x < 0.0 {
+                -t.truncate()
+            } else {
+                t.truncate()
+            };
+            *t = tangent.extend(if flip { -t.w } else { t.w });
+        }
+    }
+}
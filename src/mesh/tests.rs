@@ -36,3 +36,483 @@ fn column_integrity() {
     let mesh = ColumnMeshBuilder::new(&layout, 10.0).build();
     mesh_integrity(mesh, 6 * 4 + 12);
 }
+
+#[cfg(feature = "grid")]
+#[test]
+fn border_integrity() {
+    use crate::{BorderMeshBuilder, Hex};
+
+    let layout = HexLayout::default();
+    // Region with a single hole
+    let region = || Hex::ZERO.range(2).filter(|h| *h != Hex::ZERO);
+    let mesh = BorderMeshBuilder::new(&layout, region())
+        .with_width(0.2)
+        .build();
+    // 30 outer edges and 6 hole edges, with a repeated vertex pair per loop
+    assert_eq!(mesh.indices.len(), (30 + 6) * 6);
+    mesh_integrity(mesh.clone(), (31 + 7) * 2);
+    // Triangles face the same way as the plane mesh
+    let plane = PlaneMeshBuilder::new(&layout).build();
+    let facing = |mesh: &MeshInfo, tri: &[u32]| {
+        let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[tri[i] as usize]);
+        (b - a).cross(c - a).y > 0.0
+    };
+    let expected = facing(&plane, &plane.indices.iter().take(3).collect::<Vec<_>>());
+    let indices: Vec<u32> = mesh.indices.iter().collect();
+    for tri in indices.chunks(3) {
+        assert_eq!(facing(&mesh, tri), expected);
+    }
+    // Constant ribbon width, mitered corners are further from the boundary
+    for pair in mesh.vertices.chunks(2) {
+        let width = pair[0].distance(pair[1]);
+        assert!(width >= 0.2 - 1e-4);
+        assert!(width <= 0.2 / 30_f32.to_radians().cos() + 1e-4);
+    }
+}
+
+#[test]
+fn optimized_columns() {
+    use crate::Hex;
+
+    let layout = HexLayout::default();
+    let coords: Vec<Hex> = Hex::ZERO.range(2).collect();
+    let mut mesh = MeshInfo::default();
+    for hex in &coords {
+        let column = ColumnMeshBuilder::new(&layout, 1.0)
+            .at(*hex)
+            .without_bottom_face()
+            .build();
+        mesh.merge_with(column);
+    }
+    let optimized = mesh.clone().optimized();
+    // Only the outer side quads remain
+    let outer_sides = 6 * 5;
+    let triangles = coords.len() * 4 + outer_sides * 2;
+    assert_eq!(optimized.indices.len(), triangles * 3);
+    assert!(optimized.vertices.len() < mesh.vertices.len());
+    assert!(
+        optimized
+            .indices
+            .iter()
+            .all(|i| (i as usize) < optimized.vertices.len())
+    );
+    mesh_integrity(optimized.clone(), optimized.vertices.len());
+    // Optimizing twice changes nothing
+    let again = optimized.clone().optimized();
+    assert_eq!(again.vertices.len(), optimized.vertices.len());
+    assert_eq!(again.indices, optimized.indices);
+}
+
+#[test]
+fn merge_promotes_indices() {
+    use crate::MeshIndices;
+
+    let layout = HexLayout::default();
+    let plane = PlaneMeshBuilder::new(&layout).build();
+    let mut mesh = MeshInfo::default();
+    let count = usize::from(u16::MAX) / 6 + 1;
+    for _ in 0..count {
+        mesh.merge_with(plane.clone());
+    }
+    assert_eq!(mesh.vertices.len(), count * 6);
+    assert!(matches!(mesh.indices, MeshIndices::U32(_)));
+    assert_eq!(mesh.indices.len(), count * plane.indices.len());
+    let last = u32::try_from(mesh.vertices.len() - 1).ok();
+    assert_eq!(mesh.indices.iter().max(), last);
+}
+
+#[test]
+fn smooth_heightmap_integrity() {
+    use crate::{HeightMapMeshBuilder, Hex, SmoothOptions};
+    use std::collections::{HashMap, HashSet};
+
+    let layout = HexLayout::default();
+    let map: HashMap<Hex, f32> = Hex::ZERO
+        .range(1)
+        .map(|h| (h, layout.hex_to_world_pos(h).x + 1.0))
+        .collect();
+    let mesh = HeightMapMeshBuilder::new(&layout, &map)
+        .with_smoothing(SmoothOptions::new().with_subdivisions(1))
+        .build();
+    // 7 hexagons of 6 sectors subdivided in 4 triangles
+    assert_eq!(mesh.indices.len(), 7 * 6 * 4 * 3);
+    let len = mesh.vertices.len();
+    mesh_integrity(mesh.clone(), len);
+    // Triangles face the same way as the plane mesh, with upward normals
+    let plane = PlaneMeshBuilder::new(&layout).build();
+    let facing = |mesh: &MeshInfo, tri: &[u32]| {
+        let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[tri[i] as usize]);
+        (b - a).cross(c - a).y > 0.0
+    };
+    let expected = facing(&plane, &plane.indices.iter().take(3).collect::<Vec<_>>());
+    let indices: Vec<u32> = mesh.indices.iter().collect();
+    for tri in indices.chunks(3) {
+        assert_eq!(facing(&mesh, tri), expected);
+    }
+    // Neighboring hexagons share their edge vertices and normals:
+    // 7 centers, 24 corners, 30 edge midpoints and 42 inner midpoints
+    let snap = |v: glam::Vec3| (v * 1e3).round().as_ivec3().to_array();
+    let mut normals = HashMap::new();
+    for (position, normal) in mesh.vertices.iter().zip(&mesh.normals) {
+        assert!(normal.y > 0.0);
+        let shared = normals.entry(snap(*position)).or_insert(*normal);
+        assert!(shared.abs_diff_eq(*normal, 1e-4));
+    }
+    assert_eq!(normals.len(), 7 + 24 + 30 + 42);
+    // Flat normals
+    let mesh = HeightMapMeshBuilder::new(&layout, &map)
+        .with_smoothing(SmoothOptions::new().with_flat_normals())
+        .build();
+    let positions: HashSet<_> = mesh.vertices.iter().map(|v| snap(*v)).collect();
+    assert_eq!(positions.len(), 7 + 24);
+    // Without top face, columns are generated
+    let columns = HeightMapMeshBuilder::new(&layout, &map)
+        .without_top_face()
+        .build();
+    let mesh = HeightMapMeshBuilder::new(&layout, &map)
+        .with_smoothing(SmoothOptions::new())
+        .without_top_face()
+        .build();
+    assert!(!mesh.vertices.is_empty());
+    assert_eq!(mesh.vertices, columns.vertices);
+    assert_eq!(mesh.indices, columns.indices);
+}
+
+#[test]
+fn tangents() {
+    use glam::{Quat, Vec3};
+
+    let layout = HexLayout::default();
+    let scale = Vec3::new(2.0, 0.5, -1.5);
+    let rotation = Quat::from_rotation_arc(Vec3::Y, Vec3::new(1.0, 2.0, 3.0).normalize());
+    let mesh = ColumnMeshBuilder::new(&layout, 2.0)
+        .with_subdivisions(2)
+        .with_tangents()
+        .build();
+    assert_eq!(mesh.tangents.len(), mesh.vertices.len());
+    for (tangent, normal) in mesh.tangents.iter().zip(&mesh.normals) {
+        assert!(tangent.truncate().is_normalized());
+        assert!(tangent.truncate().dot(*normal).abs() < 1e-4);
+        assert!([1.0, -1.0].contains(&tangent.w));
+    }
+    // Transformed tangents match the ones of the transformed mesh
+    let transformed = mesh.clone().with_scale(scale).rotated(rotation);
+    let expected = ColumnMeshBuilder::new(&layout, 2.0)
+        .with_subdivisions(2)
+        .with_scale(scale)
+        .with_rotation(rotation)
+        .with_tangents()
+        .build();
+    for (a, b) in transformed.tangents.iter().zip(&expected.tangents) {
+        assert!(a.abs_diff_eq(*b, 1e-4), "{a} != {b}");
+    }
+    // Merging keeps tangents aligned with vertices
+    let mut merged = PlaneMeshBuilder::new(&layout).build();
+    merged.merge_with(mesh.clone());
+    assert_eq!(merged.tangents.len(), merged.vertices.len());
+    assert_eq!(&merged.tangents[6..], &mesh.tangents[..]);
+}
+
+#[test]
+fn vertex_attributes() {
+    use crate::{CustomAttribute, HeightMapMeshBuilder, Hex, SmoothOptions, VertexAttributes};
+    use glam::Vec4;
+    use std::collections::{HashMap, HashSet};
+
+    let layout = HexLayout::default();
+    let color = |hex: Hex| Vec4::new(0.0, 0.0, 0.0, f32::from(u8::from(hex.x > 0)));
+    let id = |hex: Hex| hex.x.unsigned_abs() * 10 + hex.y.unsigned_abs();
+    let attributes = VertexAttributes::new()
+        .with_colors(color)
+        .with_custom_u32(id);
+    // Single hexagon builders
+    let column = ColumnMeshBuilder::new(&layout, 1.0)
+        .at(Hex::new(1, 2))
+        .with_vertex_attributes(&attributes)
+        .build();
+    assert!(column.colors.iter().all(|c| *c == Vec4::W));
+    assert_eq!(
+        column.custom,
+        Some(CustomAttribute::U32(vec![12; column.vertices.len()]))
+    );
+    // Merging pads missing attributes
+    let mut merged = column.clone();
+    merged.merge_with(PlaneMeshBuilder::new(&layout).build());
+    assert_eq!(merged.colors.len(), merged.vertices.len());
+    assert_eq!(&merged.colors[column.vertices.len()..], &[Vec4::ONE; 6]);
+    assert!(
+        merged
+            .custom
+            .as_ref()
+            .is_some_and(|c| c.len() == merged.vertices.len())
+    );
+    // Height maps, every vertex has the values of one of the hexagons
+    let map: HashMap<Hex, f32> = Hex::ZERO
+        .range(2)
+        .map(|h| (h, layout.hex_to_world_pos(h).y))
+        .collect();
+    let expected: HashSet<_> = map.keys().map(|h| (id(*h), color(*h).w > 0.0)).collect();
+    for smoothing in [None, Some(SmoothOptions::new().with_subdivisions(1))] {
+        let mut builder =
+            HeightMapMeshBuilder::new(&layout, &map).with_vertex_attributes(&attributes);
+        builder.smoothing = smoothing;
+        let mesh = builder.build();
+        assert_eq!(mesh.colors.len(), mesh.vertices.len());
+        let Some(CustomAttribute::U32(ids)) = mesh.custom else {
+            panic!("Missing custom attribute");
+        };
+        let values: HashSet<_> = ids
+            .into_iter()
+            .zip(mesh.colors)
+            .map(|(id, color)| (id, color.w > 0.0))
+            .collect();
+        assert_eq!(values, expected);
+    }
+}
+
+#[cfg(feature = "grid")]
+#[test]
+fn lod_chunks() {
+    use crate::{Hex, LodMeshBuilder};
+    use glam::Vec3;
+    use std::collections::{HashMap, HashSet};
+
+    let layout = HexLayout::default();
+    let map: HashMap<Hex, f32> = Hex::ZERO
+        .range(20)
+        .map(|h| (h, layout.hex_to_world_pos(h).x.sin()))
+        .collect();
+    let chunk = Hex::new(1, -1);
+    let radius = 5;
+    let plane = PlaneMeshBuilder::new(&layout).build();
+    let triangles = |mesh: &MeshInfo| -> Vec<[Vec3; 3]> {
+        let indices: Vec<u32> = mesh.indices.iter().collect();
+        indices
+            .chunks(3)
+            .map(|t| [t[0], t[1], t[2]].map(|i| mesh.vertices[i as usize]))
+            .collect()
+    };
+    let area = |[a, b, c]: [Vec3; 3]| (b - a).cross(c - a).y / 2.0;
+    let hex_area: f32 = triangles(&plane).into_iter().map(area).sum();
+    let snap = |v: Vec3| (v * 1e3).round().as_ivec3().to_array();
+    let mut outline = None;
+    let mut previous_len = usize::MAX;
+    for lod in 0..=3 {
+        let mesh = LodMeshBuilder::new(&layout, &map, chunk, radius)
+            .with_lod(lod)
+            .with_skirt_depth(2.0)
+            .build();
+        let len = mesh.vertices.len();
+        mesh_integrity(mesh.clone(), len);
+        assert!(mesh.indices.len() < previous_len);
+        previous_len = mesh.indices.len();
+        // Top faces cover the whole chunk, facing up
+        let top: Vec<_> = triangles(&mesh)
+            .into_iter()
+            .filter(|[a, b, c]| (b - a).cross(c - a).normalize().y.abs() > 0.99)
+            .collect();
+        let top_area: f32 = top.iter().copied().map(area).sum();
+        assert!(top.iter().all(|t| area(*t) > 0.0));
+        #[expect(clippy::cast_precision_loss)]
+        let expected = Hex::range_count(radius) as f32 * hex_area;
+        assert!((top_area - expected).abs() < 1e-2);
+        // The skirt outline is the same at every level
+        let skirts = LodMeshBuilder::new(&layout, &map, chunk, radius)
+            .with_lod(lod)
+            .without_sides()
+            .build();
+        let skirt: HashSet<_> = skirts
+            .vertices
+            .iter()
+            .zip(&skirts.normals)
+            .filter(|(_, n)| n.y.abs() < 1e-4)
+            .map(|(v, _)| {
+                let [x, _, z] = snap(*v);
+                [x, z]
+            })
+            .collect();
+        assert_eq!(*outline.get_or_insert_with(|| skirt.clone()), skirt);
+    }
+}
+
+#[cfg(feature = "mesh_export")]
+#[test]
+fn export_formats() {
+    use crate::VertexAttributes;
+    use glam::Vec4;
+
+    let layout = HexLayout::default();
+    let attributes = VertexAttributes::new().with_colors(|_| Vec4::new(1.0, 0.5, 0.0, 1.0));
+    let mesh = ColumnMeshBuilder::new(&layout, 1.0)
+        .with_vertex_attributes(&attributes)
+        .with_tangents()
+        .build();
+    let [vertices, triangles] = [mesh.vertices.len(), mesh.indices.len() / 3];
+    // OBJ
+    let mut obj = Vec::new();
+    assert!(mesh.write_obj(&mut obj).is_ok());
+    let obj = String::from_utf8(obj).unwrap_or_default();
+    let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count("v "), vertices);
+    assert_eq!(count("vt "), vertices);
+    assert_eq!(count("vn "), vertices);
+    assert_eq!(count("f "), triangles);
+    // Vertex colors
+    assert!(
+        obj.lines()
+            .filter(|l| l.starts_with("v "))
+            .all(|l| l.ends_with(" 1 0.5 0"))
+    );
+    // PLY
+    let mut ply = Vec::new();
+    assert!(mesh.write_ply(&mut ply).is_ok());
+    let header_end = b"end_header\n";
+    let header_len = ply
+        .windows(header_end.len())
+        .position(|w| w == header_end)
+        .map(|p| p + header_end.len());
+    let expected_len = vertices * (8 * 4 + 4) + triangles * (1 + 3 * 4);
+    assert_eq!(header_len.map(|h| ply.len() - h), Some(expected_len));
+    // GLB
+    let mut glb = Vec::new();
+    assert!(mesh.write_glb(&mut glb).is_ok());
+    let read_u32 = |offset: usize| {
+        let bytes: [u8; 4] = glb[offset..offset + 4].try_into().unwrap_or_default();
+        u32::from_le_bytes(bytes) as usize
+    };
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(read_u32(4), 2);
+    assert_eq!(read_u32(8), glb.len());
+    let json_len = read_u32(12);
+    assert_eq!(&glb[16..20], b"JSON");
+    assert_eq!(json_len % 4, 0);
+    let json = String::from_utf8(glb[20..20 + json_len].to_vec()).unwrap_or_default();
+    for attribute in ["POSITION", "NORMAL", "TEXCOORD_0", "TANGENT", "COLOR_0"] {
+        assert!(json.contains(&format!("\"{attribute}\":")));
+    }
+    let bin_len = read_u32(20 + json_len);
+    assert_eq!(&glb[24 + json_len..28 + json_len], b"BIN\0");
+    assert_eq!(28 + json_len + bin_len, glb.len());
+    assert!(json.contains(&format!("\"byteLength\":{bin_len}}}]")));
+    let attribute_bytes = vertices * (3 + 3 + 2 + 4 + 4) * 4;
+    assert_eq!(
+        bin_len,
+        attribute_bytes + (triangles * 3 * 2).next_multiple_of(4)
+    );
+}
+
+#[cfg(feature = "bevy_mesh")]
+#[test]
+fn bevy_mesh_conversion() {
+    use crate::{Hex, VertexAttributes};
+    use bevy_asset::RenderAssetUsages;
+    use bevy_mesh::{Indices, Mesh, VertexAttributeValues};
+    use glam::{Vec3, Vec4};
+
+    let layout = HexLayout::default();
+    let attributes = VertexAttributes::new()
+        .with_colors(|_| Vec4::ONE)
+        .with_custom_u32(|hex| hex.x.unsigned_abs());
+    let info = ColumnMeshBuilder::new(&layout, 1.0)
+        .with_vertex_attributes(&attributes)
+        .with_tangents()
+        .build();
+    let len = info.vertices.len();
+    let mesh = Mesh::from(info);
+    for attribute in [
+        Mesh::ATTRIBUTE_POSITION,
+        Mesh::ATTRIBUTE_NORMAL,
+        Mesh::ATTRIBUTE_UV_0,
+        Mesh::ATTRIBUTE_TANGENT,
+        Mesh::ATTRIBUTE_COLOR,
+        MeshInfo::ATTRIBUTE_CUSTOM_U32,
+    ] {
+        assert_eq!(
+            mesh.attribute(attribute).map(VertexAttributeValues::len),
+            Some(len)
+        );
+    }
+    assert!(matches!(mesh.indices(), Some(Indices::U16(_))));
+    assert_eq!(mesh.asset_usage, RenderAssetUsages::default());
+    // 2D
+    let hex = Hex::new(2, -3);
+    let info = PlaneMeshBuilder::new(&layout).at(hex).build();
+    let mesh = info.into_bevy_mesh_2d(RenderAssetUsages::RENDER_WORLD);
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("Missing positions");
+    };
+    let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    else {
+        panic!("Missing normals");
+    };
+    let center = layout.hex_to_world_pos(hex);
+    for p in positions {
+        let dist = Vec3::from_array(*p).truncate().distance(center);
+        assert!(dist < 1e-4 || (dist - layout.scale.x).abs() < 1e-4);
+    }
+    assert!(
+        normals
+            .iter()
+            .all(|n| Vec3::from_array(*n).abs_diff_eq(Vec3::Z, 1e-6))
+    );
+    let positions: Vec<Vec3> = positions.iter().copied().map(Vec3::from_array).collect();
+    let indices: Vec<usize> = mesh.indices().into_iter().flat_map(Indices::iter).collect();
+    for t in indices.chunks_exact(3) {
+        let [a, b, c] = [positions[t[0]], positions[t[1]], positions[t[2]]];
+        assert!((b - a).cross(c - a).z > 0.0);
+    }
+}
+
+#[cfg(feature = "mesh_export")]
+#[test]
+fn export_partial_channels() {
+    use glam::Vec4;
+
+    let mut mesh = PlaneMeshBuilder::new(&HexLayout::default()).build();
+    let write_obj = |mesh: &MeshInfo| {
+        let mut obj = Vec::new();
+        assert!(mesh.write_obj(&mut obj).is_ok());
+        String::from_utf8(obj).unwrap_or_default()
+    };
+    let first_face = |obj: &str| obj.lines().find(|l| l.starts_with("f ")).map(str::to_owned);
+    assert_eq!(
+        first_face(&write_obj(&mesh)).as_deref(),
+        Some("f 1/1/1 3/3/3 2/2/2")
+    );
+    // Partial colors are ignored
+    mesh.colors.push(Vec4::ONE);
+    assert!(
+        write_obj(&mesh)
+            .lines()
+            .all(|l| !l.starts_with("v ") || l.split(' ').count() == 4)
+    );
+    mesh.uvs.pop();
+    let obj = write_obj(&mesh);
+    assert!(!obj.contains("vt "));
+    assert_eq!(first_face(&obj).as_deref(), Some("f 1//1 3//3 2//2"));
+    mesh.normals.clear();
+    let obj = write_obj(&mesh);
+    assert!(!obj.contains("vn "));
+    assert_eq!(first_face(&obj).as_deref(), Some("f 1 3 2"));
+    // glTF accessors must cover every vertex
+    let mut glb = Vec::new();
+    assert!(mesh.write_glb(&mut glb).is_ok());
+    let glb = String::from_utf8_lossy(&glb);
+    assert!(glb.contains("\"POSITION\":"));
+    assert!(!glb.contains("\"NORMAL\":"));
+    assert!(!glb.contains("\"TEXCOORD_0\":"));
+    // PLY only declares the complete channels
+    let mut ply = Vec::new();
+    assert!(mesh.write_ply(&mut ply).is_ok());
+    let header_end = b"end_header\n";
+    let header_len = ply
+        .windows(header_end.len())
+        .position(|w| w == header_end)
+        .map(|p| p + header_end.len());
+    let header = String::from_utf8_lossy(&ply[..header_len.unwrap_or_default()]);
+    assert!(header.contains("property float z\nelement face"));
+    let expected_len = mesh.vertices.len() * 3 * 4 + mesh.indices.len() / 3 * (1 + 3 * 4);
+    assert_eq!(header_len.map(|h| ply.len() - h), Some(expected_len));
+}
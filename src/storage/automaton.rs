@@ -0,0 +1,218 @@
+use crate::Hex;
+
+use super::HexStoreStep;
+
+/// Double-buffered cellular automaton over a dense storage, for fire spread,
+/// fluid or erosion simulations.
+///
+/// Every [`Self::step`] computes the next generation into a second buffer,
+/// with a `rule` called for every coordinate with its current value and the
+/// values of its neighbors, then swaps both buffers. No allocation is made
+/// after construction.
+///
+/// > See [`HexStoreStep`] for the supported storages
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::*};
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Cell {
+///     Tree,
+///     Fire,
+///     Ash,
+/// }
+///
+/// let forest = HexagonalMap::new(Hex::ZERO, 10, |coord| {
+///     if coord == Hex::ZERO {
+///         Cell::Fire
+///     } else {
+///         Cell::Tree
+///     }
+/// });
+/// let mut automaton = CellularAutomaton::new(forest);
+/// let spread = |_, cell: &Cell, neighbors: [Option<&Cell>; 6]| match cell {
+///     Cell::Tree if neighbors.contains(&Some(&Cell::Fire)) => Cell::Fire,
+///     Cell::Fire | Cell::Ash => Cell::Ash,
+///     Cell::Tree => Cell::Tree,
+/// };
+/// automaton.step(spread);
+/// automaton.step(spread);
+/// assert_eq!(automaton.generation(), 2);
+/// let forest = automaton.current();
+/// assert_eq!(forest[Hex::ZERO], Cell::Ash);
+/// assert_eq!(forest[hex(1, 0)], Cell::Ash);
+/// assert_eq!(forest[hex(2, 0)], Cell::Fire);
+/// assert_eq!(forest[hex(3, 0)], Cell::Tree);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CellularAutomaton<S> {
+    current: S,
+    buffer: S,
+    generation: u64,
+}
+
+impl<S> CellularAutomaton<S> {
+    /// Creates an automaton starting from `map`, cloned for the second buffer
+    #[must_use]
+    pub fn new(map: S) -> Self
+    where
+        S: Clone,
+    {
+        Self {
+            buffer: map.clone(),
+            current: map,
+            generation: 0,
+        }
+    }
+
+    /// Returns the current generation
+    #[inline]
+    #[must_use]
+    pub const fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// Returns the current generation mutably, to edit values between steps
+    #[inline]
+    #[must_use]
+    pub const fn current_mut(&mut self) -> &mut S {
+        &mut self.current
+    }
+
+    /// Returns the amount of computed steps
+    #[inline]
+    #[must_use]
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the current generation, dropping the second buffer
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.current
+    }
+
+    /// Computes the next generation with `rule`, called for every coordinate
+    /// with its current value and the values of its neighbors.
+    ///
+    /// > See [`HexStoreStep::step_into`]
+    pub fn step<T>(&mut self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T)
+    where
+        S: HexStoreStep<T>,
+    {
+        self.current.step_into(&mut self.buffer, rule);
+        self.swap();
+    }
+
+    /// Computes the next generation with `rule` using parallel processing
+    /// with `rayon`
+    ///
+    /// > See [`Self::step`]
+    #[cfg(feature = "rayon")]
+    pub fn step_parallel<T>(&mut self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T + Sync)
+    where
+        S: HexStoreStep<T>,
+        T: Send + Sync,
+    {
+        self.current.step_into_parallel(&mut self.buffer, rule);
+        self.swap();
+    }
+
+    const fn swap(&mut self) {
+        std::mem::swap(&mut self.current, &mut self.buffer);
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hex,
+        storage::{HexModMap, HexStore, HexagonalMap, RectMetadata, RombusMap, WrapStrategy},
+    };
+    #[cfg(feature = "bevy_platform")]
+    use bevy_platform::collections::HashMap;
+    #[cfg(not(feature = "bevy_platform"))]
+    use std::collections::HashMap;
+
+    /// Counts the neighbors with a greater value, wrapping around 5
+    #[expect(clippy::trivially_copy_pass_by_ref, clippy::cast_possible_truncation)]
+    fn rule(hex: Hex, value: &u32, neighbors: [Option<&u32>; 6]) -> u32 {
+        let greater = neighbors.iter().flatten().filter(|n| **n > value).count();
+        (value + greater as u32 + hex.ulength()) % 5
+    }
+
+    /// Reference implementation on a hash map
+    fn expected<S: HexStore<u32>>(map: &S, steps: u64) -> HashMap<Hex, u32> {
+        let mut current: HashMap<Hex, u32> = map.iter().map(|(h, v)| (h, *v)).collect();
+        for _ in 0..steps {
+            current = current
+                .iter()
+                .map(|(h, v)| {
+                    let neighbors = h.all_neighbors().map(|n| current.get(&n));
+                    (*h, rule(*h, v, neighbors))
+                })
+                .collect();
+        }
+        current
+    }
+
+    fn check<S>(map: S)
+    where
+        S: HexStoreStep<u32> + Clone,
+    {
+        let expected = expected(&map, 10);
+        let mut automaton = CellularAutomaton::new(map);
+        for _ in 0..10 {
+            automaton.step(rule);
+        }
+        assert_eq!(automaton.generation(), 10);
+        let result: HashMap<Hex, u32> = automaton.current().iter().map(|(h, v)| (h, *v)).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn dense_storages() {
+        let init = |h: Hex| (h.x * 7 + h.y * 3).unsigned_abs() % 5;
+        check(HexagonalMap::new(hex(1, 2), 6, init));
+        check(HexModMap::new(hex(1, 2), 6, init));
+        check(RombusMap::new(hex(-3, 2), 7, 9, init));
+        // Clamped on both axis to match the non wrapping reference
+        let meta =
+            RectMetadata::from_half_size([5, 4]).with_wrap_strategies([WrapStrategy::Clamp; 2]);
+        check(meta.build(init));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel() {
+        let init = |h: Hex| (h.x * 7 + h.y * 3).unsigned_abs() % 5;
+        let meta = RectMetadata::from_half_size([5, 4]);
+        let mut sequential = CellularAutomaton::new(meta.build(init));
+        let mut parallel = sequential.clone();
+        let mut sequential_hexagonal =
+            CellularAutomaton::new(HexagonalMap::new(Hex::ZERO, 6, init));
+        let mut parallel_hexagonal = sequential_hexagonal.clone();
+        for _ in 0..10 {
+            sequential.step(rule);
+            parallel.step_parallel(rule);
+            sequential_hexagonal.step(rule);
+            parallel_hexagonal.step_parallel(rule);
+        }
+        assert!(
+            sequential
+                .current()
+                .values()
+                .eq(parallel.current().values())
+        );
+        assert!(
+            sequential_hexagonal
+                .current()
+                .values()
+                .eq(parallel_hexagonal.current().values())
+        );
+    }
+}
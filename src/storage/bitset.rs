@@ -0,0 +1,658 @@
+use crate::{Hex, HexBounds, hex::ExactSizeHexIterator};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+
+use super::RectMetadata;
+
+pub(super) const WORD_BITS: usize = u64::BITS as usize;
+
+/// Returns the bit at `index`
+#[inline]
+pub(super) fn get_bit(words: &[u64], index: usize) -> bool {
+    words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+}
+
+/// Sets the bit at `index` to `value`, returning `true` if it changed
+#[inline]
+pub(super) fn set_bit(words: &mut [u64], index: usize, value: bool) -> bool {
+    let word = &mut words[index / WORD_BITS];
+    let bit = 1 << (index % WORD_BITS);
+    let previous = *word & bit != 0;
+    if value {
+        *word |= bit;
+    } else {
+        *word &= !bit;
+    }
+    previous != value
+}
+
+/// Iterates over the indices of the set bits
+pub(super) fn iter_ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(i, word)| {
+        let mut word = *word;
+        std::iter::from_fn(move || {
+            if word == 0 {
+                return None;
+            }
+            let bit = word.trailing_zeros() as usize;
+            word &= word - 1;
+            Some(i * WORD_BITS + bit)
+        })
+    })
+}
+
+/// Counts the set bits
+fn count_ones(words: &[u64]) -> usize {
+    words.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+/// Hexagonal hexmod index mapping, matching [`HexModMap`](super::HexModMap)
+const fn hexmod_index(bounds: &HexBounds, hex: Hex) -> Option<usize> {
+    if !bounds.is_in_bounds(hex) {
+        return None;
+    }
+    Some(
+        hex.const_sub(bounds.center)
+            .to_hexmod_coordinates(bounds.radius) as usize,
+    )
+}
+
+#[expect(clippy::cast_possible_truncation)]
+const fn hexmod_coord(bounds: &HexBounds, index: usize) -> Hex {
+    bounds
+        .center
+        .const_add(Hex::from_hexmod_coordinates(index as u32, bounds.radius))
+}
+
+/// Index mapping of a [`HexBitSet`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum BitSetShape {
+    Hexagonal(HexBounds),
+    Rect(RectMetadata),
+}
+
+impl BitSetShape {
+    fn len(&self) -> usize {
+        match self {
+            Self::Hexagonal(bounds) => bounds.hex_count(),
+            Self::Rect(meta) => meta.len(),
+        }
+    }
+
+    fn index(&self, hex: Hex) -> Option<usize> {
+        match self {
+            Self::Hexagonal(bounds) => hexmod_index(bounds, hex),
+            Self::Rect(meta) => meta.hex_to_idx(hex),
+        }
+    }
+
+    fn coord(&self, index: usize) -> Hex {
+        match self {
+            Self::Hexagonal(bounds) => hexmod_coord(bounds, index),
+            Self::Rect(meta) => meta.idx_to_hex(index),
+        }
+    }
+}
+
+/// Bit-packed set of coordinates for dense maps, using a single bit per
+/// coordinate.
+///
+/// The set has fixed bounds, either hexagonal with the same index mapping as
+/// [`HexModMap`](super::HexModMap) or rectangular with the same index mapping
+/// as [`RectMap`](super::RectMap). Coordinates out of bounds are never
+/// contained.
+///
+/// This fits boolean layers, like visibility, exploration or passability
+/// flags, of large maps.
+///
+/// > See [`ChunkedHexBitSet`] for unbounded sets
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::*};
+/// # use std::collections::HashSet;
+/// let bounds = HexBounds::new(Hex::ZERO, 20);
+/// let mut visible = HexBitSet::hexagonal(bounds);
+/// let mut explored = HexBitSet::hexagonal(bounds);
+/// visible.extend(Hex::ZERO.range(3));
+/// explored.extend(hex(2, 0).range(3));
+/// explored |= &visible;
+/// let expected: HashSet<Hex> = Hex::ZERO.range(3).chain(hex(2, 0).range(3)).collect();
+/// assert_eq!(explored.len(), expected.len());
+/// let newly_visible = &visible - &explored;
+/// assert!(newly_visible.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexBitSet {
+    words: Vec<u64>,
+    shape: BitSetShape,
+    len: usize,
+}
+
+impl HexBitSet {
+    fn with_shape(shape: BitSetShape) -> Self {
+        Self {
+            words: vec![0; shape.len().div_ceil(WORD_BITS)],
+            shape,
+            len: 0,
+        }
+    }
+
+    /// Creates an empty hexagon shaped set, with the index mapping of
+    /// [`HexModMap`](super::HexModMap)
+    #[must_use]
+    pub fn hexagonal(bounds: HexBounds) -> Self {
+        Self::with_shape(BitSetShape::Hexagonal(bounds))
+    }
+
+    /// Creates an empty rectangle shaped set, with the index mapping of
+    /// [`RectMap`](super::RectMap)
+    #[must_use]
+    pub fn rect(meta: RectMetadata) -> Self {
+        Self::with_shape(BitSetShape::Rect(meta))
+    }
+
+    /// Returns the amount of set coordinates
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no coordinate is set
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the amount of coordinates in bounds
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// Returns `true` if `hex` is in the set bounds
+    #[inline]
+    #[must_use]
+    pub fn is_in_bounds(&self, hex: Hex) -> bool {
+        self.shape.index(hex).is_some()
+    }
+
+    /// Returns `true` if `hex` is set
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.shape
+            .index(hex)
+            .is_some_and(|i| get_bit(&self.words, i))
+    }
+
+    /// Sets `hex`, returning `true` if it wasn't set.
+    ///
+    /// Out of bounds coordinates are ignored and return `false`
+    pub fn insert(&mut self, hex: Hex) -> bool {
+        let Some(index) = self.shape.index(hex) else {
+            return false;
+        };
+        let inserted = set_bit(&mut self.words, index, true);
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Unsets `hex`, returning `true` if it was set
+    pub fn remove(&mut self, hex: Hex) -> bool {
+        let Some(index) = self.shape.index(hex) else {
+            return false;
+        };
+        let removed = set_bit(&mut self.words, index, false);
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    /// Unsets every coordinate
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.len = 0;
+    }
+
+    /// Iterator over the set coordinates
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Hex> + '_ {
+        ExactSizeHexIterator {
+            iter: iter_ones(&self.words).map(|i| self.shape.coord(i)),
+            count: self.len,
+        }
+    }
+
+    /// Retains only the coordinates specified by the predicate
+    pub fn retain(&mut self, mut f: impl FnMut(Hex) -> bool) {
+        let removed: Vec<_> = self.iter().filter(|h| !f(*h)).collect();
+        for hex in removed {
+            self.remove(hex);
+        }
+    }
+
+    /// Sets every coordinate of `other`.
+    ///
+    /// If the sets have different bounds, coordinates of `other` out of
+    /// the bounds of `self` are ignored
+    pub fn union_with(&mut self, other: &Self) {
+        if self.shape == other.shape {
+            for (a, b) in self.words.iter_mut().zip(&other.words) {
+                *a |= b;
+            }
+            self.len = count_ones(&self.words);
+        } else {
+            self.extend(other.iter());
+        }
+    }
+
+    /// Unsets every coordinate which is not in `other`
+    pub fn intersect_with(&mut self, other: &Self) {
+        if self.shape == other.shape {
+            for (a, b) in self.words.iter_mut().zip(&other.words) {
+                *a &= b;
+            }
+            self.len = count_ones(&self.words);
+        } else {
+            self.retain(|h| other.contains(h));
+        }
+    }
+
+    /// Unsets every coordinate of `other`
+    pub fn difference_with(&mut self, other: &Self) {
+        if self.shape == other.shape {
+            for (a, b) in self.words.iter_mut().zip(&other.words) {
+                *a &= !b;
+            }
+            self.len = count_ones(&self.words);
+        } else {
+            for hex in other.iter() {
+                self.remove(hex);
+            }
+        }
+    }
+}
+
+impl Extend<Hex> for HexBitSet {
+    /// Sets every coordinate, ignoring the out of bounds ones
+    fn extend<I: IntoIterator<Item = Hex>>(&mut self, iter: I) {
+        for hex in iter {
+            self.insert(hex);
+        }
+    }
+}
+
+impl FromIterator<Hex> for HexBitSet {
+    /// Creates a hexagonal set with the smallest bounds containing every
+    /// coordinate
+    fn from_iter<I: IntoIterator<Item = Hex>>(iter: I) -> Self {
+        let coords: Vec<Hex> = iter.into_iter().collect();
+        let mut set = Self::hexagonal(coords.iter().copied().collect());
+        set.extend(coords);
+        set
+    }
+}
+
+/// Bit-packed set of coordinates for unbounded maps, lazily allocating
+/// hexagonal chunks of bits.
+///
+/// Coordinates are grouped in chunks using [`Hex::to_lower_res`], like
+/// [`ChunkedMap`](super::ChunkedMap), and empty chunks are deallocated.
+///
+/// > See [`HexBitSet`] for bounded sets
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::*};
+/// let mut explored: ChunkedHexBitSet = hex(1_000, -300).range(10).collect();
+/// let visible: ChunkedHexBitSet = hex(1_010, -300).range(10).collect();
+/// let newly_explored = &visible - &explored;
+/// explored |= &visible;
+/// assert!(newly_explored.iter().all(|h| explored.contains(h)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkedHexBitSet {
+    chunks: HashMap<Hex, Vec<u64>>,
+    chunk_radius: u32,
+    len: usize,
+}
+
+impl Default for ChunkedHexBitSet {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CHUNK_RADIUS)
+    }
+}
+
+impl ChunkedHexBitSet {
+    /// Chunk radius used by [`Default`] and [`FromIterator`]
+    pub const DEFAULT_CHUNK_RADIUS: u32 = 8;
+
+    /// Creates an empty set with hexagonal chunks of the given `chunk_radius`
+    #[must_use]
+    pub fn new(chunk_radius: u32) -> Self {
+        Self {
+            chunks: HashMap::default(),
+            chunk_radius,
+            len: 0,
+        }
+    }
+
+    /// Returns the radius of the chunks
+    #[inline]
+    #[must_use]
+    pub const fn chunk_radius(&self) -> u32 {
+        self.chunk_radius
+    }
+
+    /// Returns the amount of set coordinates
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no coordinate is set
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the amount of allocated chunks
+    #[inline]
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the chunk coordinate and the chunk bounds of `hex`
+    fn locate(&self, hex: Hex) -> (Hex, HexBounds) {
+        let chunk = hex.to_lower_res(self.chunk_radius);
+        let bounds = HexBounds::new(chunk.to_higher_res(self.chunk_radius), self.chunk_radius);
+        (chunk, bounds)
+    }
+
+    /// Returns `true` if `hex` is set
+    #[must_use]
+    pub fn contains(&self, hex: Hex) -> bool {
+        let (chunk, bounds) = self.locate(hex);
+        self.chunks
+            .get(&chunk)
+            .zip(hexmod_index(&bounds, hex))
+            .is_some_and(|(words, i)| get_bit(words, i))
+    }
+
+    /// Sets `hex`, returning `true` if it wasn't set
+    pub fn insert(&mut self, hex: Hex) -> bool {
+        let (chunk, bounds) = self.locate(hex);
+        let Some(index) = hexmod_index(&bounds, hex) else {
+            return false;
+        };
+        let words = self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| vec![0; bounds.hex_count().div_ceil(WORD_BITS)]);
+        let inserted = set_bit(words, index, true);
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Unsets `hex`, returning `true` if it was set
+    pub fn remove(&mut self, hex: Hex) -> bool {
+        let (chunk, bounds) = self.locate(hex);
+        let (Some(words), Some(index)) = (self.chunks.get_mut(&chunk), hexmod_index(&bounds, hex))
+        else {
+            return false;
+        };
+        let removed = set_bit(words, index, false);
+        if words.iter().all(|w| *w == 0) {
+            self.chunks.remove(&chunk);
+        }
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    /// Unsets every coordinate
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+    }
+
+    /// Iterator over the set coordinates, in arbitrary order
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Hex> + '_ {
+        let radius = self.chunk_radius;
+        let iter = self.chunks.iter().flat_map(move |(chunk, words)| {
+            let bounds = HexBounds::new(chunk.to_higher_res(radius), radius);
+            iter_ones(words).map(move |i| hexmod_coord(&bounds, i))
+        });
+        ExactSizeHexIterator {
+            iter,
+            count: self.len,
+        }
+    }
+
+    /// Retains only the coordinates specified by the predicate
+    pub fn retain(&mut self, mut f: impl FnMut(Hex) -> bool) {
+        let removed: Vec<_> = self.iter().filter(|h| !f(*h)).collect();
+        for hex in removed {
+            self.remove(hex);
+        }
+    }
+
+    fn recount(&mut self) {
+        self.chunks.retain(|_, words| words.iter().any(|w| *w != 0));
+        self.len = self.chunks.values().map(|words| count_ones(words)).sum();
+    }
+
+    /// Sets every coordinate of `other`
+    pub fn union_with(&mut self, other: &Self) {
+        if self.chunk_radius == other.chunk_radius {
+            for (chunk, words) in &other.chunks {
+                let target = self
+                    .chunks
+                    .entry(*chunk)
+                    .or_insert_with(|| vec![0; words.len()]);
+                for (a, b) in target.iter_mut().zip(words) {
+                    *a |= b;
+                }
+            }
+            self.recount();
+        } else {
+            self.extend(other.iter());
+        }
+    }
+
+    /// Unsets every coordinate which is not in `other`
+    pub fn intersect_with(&mut self, other: &Self) {
+        if self.chunk_radius == other.chunk_radius {
+            self.chunks.retain(|chunk, words| {
+                let Some(other) = other.chunks.get(chunk) else {
+                    return false;
+                };
+                for (a, b) in words.iter_mut().zip(other) {
+                    *a &= b;
+                }
+                true
+            });
+            self.recount();
+        } else {
+            self.retain(|h| other.contains(h));
+        }
+    }
+
+    /// Unsets every coordinate of `other`
+    pub fn difference_with(&mut self, other: &Self) {
+        if self.chunk_radius == other.chunk_radius {
+            for (chunk, words) in &mut self.chunks {
+                if let Some(other) = other.chunks.get(chunk) {
+                    for (a, b) in words.iter_mut().zip(other) {
+                        *a &= !b;
+                    }
+                }
+            }
+            self.recount();
+        } else {
+            for hex in other.iter() {
+                self.remove(hex);
+            }
+        }
+    }
+}
+
+impl Extend<Hex> for ChunkedHexBitSet {
+    fn extend<I: IntoIterator<Item = Hex>>(&mut self, iter: I) {
+        for hex in iter {
+            self.insert(hex);
+        }
+    }
+}
+
+impl FromIterator<Hex> for ChunkedHexBitSet {
+    /// Creates a set with chunks of [`ChunkedHexBitSet::DEFAULT_CHUNK_RADIUS`]
+    fn from_iter<I: IntoIterator<Item = Hex>>(iter: I) -> Self {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+macro_rules! bitset_ops {
+    ($ty:ty) => {
+        impl std::ops::BitOrAssign<&$ty> for $ty {
+            fn bitor_assign(&mut self, rhs: &$ty) {
+                self.union_with(rhs);
+            }
+        }
+
+        impl std::ops::BitAndAssign<&$ty> for $ty {
+            fn bitand_assign(&mut self, rhs: &$ty) {
+                self.intersect_with(rhs);
+            }
+        }
+
+        impl std::ops::SubAssign<&$ty> for $ty {
+            fn sub_assign(&mut self, rhs: &$ty) {
+                self.difference_with(rhs);
+            }
+        }
+
+        impl std::ops::BitOr for &$ty {
+            type Output = $ty;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                let mut res = self.clone();
+                res.union_with(rhs);
+                res
+            }
+        }
+
+        impl std::ops::BitAnd for &$ty {
+            type Output = $ty;
+
+            fn bitand(self, rhs: Self) -> Self::Output {
+                let mut res = self.clone();
+                res.intersect_with(rhs);
+                res
+            }
+        }
+
+        impl std::ops::Sub for &$ty {
+            type Output = $ty;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                let mut res = self.clone();
+                res.difference_with(rhs);
+                res
+            }
+        }
+    };
+}
+
+bitset_ops!(HexBitSet);
+bitset_ops!(ChunkedHexBitSet);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    #[cfg(feature = "bevy_platform")]
+    use bevy_platform::collections::HashSet;
+    #[cfg(not(feature = "bevy_platform"))]
+    use std::collections::HashSet;
+
+    fn sorted(iter: impl Iterator<Item = Hex>) -> Vec<Hex> {
+        let mut res: Vec<_> = iter.collect();
+        res.sort_unstable_by_key(|h| h.to_array());
+        res
+    }
+
+    #[test]
+    fn index_mappings() {
+        for mut set in [
+            HexBitSet::hexagonal(HexBounds::new(hex(3, -2), 7)),
+            HexBitSet::rect(RectMetadata::from_half_size([6, 4])),
+        ] {
+            let coords = || hex(3, -2).range(20);
+            for coord in coords() {
+                assert_eq!(set.insert(coord), set.is_in_bounds(coord));
+            }
+            assert_eq!(set.len(), set.capacity());
+            let expected = coords().filter(|h| set.is_in_bounds(*h));
+            assert_eq!(sorted(set.iter()), sorted(expected));
+            for coord in coords() {
+                assert_eq!(set.remove(coord), set.is_in_bounds(coord));
+            }
+            assert!(set.is_empty());
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: Vec<Hex> = Hex::ZERO.range(6).collect();
+        let b: Vec<Hex> = hex(4, -1).range(5).collect();
+        let [set_a, set_b]: [HashSet<Hex>; 2] = [&a, &b].map(|v| v.iter().copied().collect());
+        let union = sorted(set_a.union(&set_b).copied());
+        let inter = sorted(set_a.intersection(&set_b).copied());
+        let diff = sorted(set_a.difference(&set_b).copied());
+
+        let bounds = HexBounds::new(Hex::ZERO, 12);
+        let mut dense_a = HexBitSet::hexagonal(bounds);
+        dense_a.extend(a.iter().copied());
+        let mut dense_b = HexBitSet::hexagonal(bounds);
+        dense_b.extend(b.iter().copied());
+        // Different bounds
+        let other_b: HexBitSet = b.iter().copied().collect();
+        for rhs in [&dense_b, &other_b] {
+            assert_eq!(sorted((&dense_a | rhs).iter()), union);
+            assert_eq!(sorted((&dense_a & rhs).iter()), inter);
+            assert_eq!(sorted((&dense_a - rhs).iter()), diff);
+            assert_eq!((&dense_a | rhs).len(), union.len());
+        }
+
+        let chunked_a: ChunkedHexBitSet = a.iter().copied().collect();
+        let mut chunked_b = ChunkedHexBitSet::new(2);
+        chunked_b.extend(b.iter().copied());
+        let same_b: ChunkedHexBitSet = b.iter().copied().collect();
+        for rhs in [&chunked_b, &same_b] {
+            assert_eq!(sorted((&chunked_a | rhs).iter()), union);
+            assert_eq!(sorted((&chunked_a & rhs).iter()), inter);
+            assert_eq!(sorted((&chunked_a - rhs).iter()), diff);
+            assert_eq!((&chunked_a - rhs).len(), diff.len());
+        }
+        let mut empty = chunked_a.clone();
+        empty -= &chunked_a;
+        assert!(empty.is_empty());
+        assert_eq!(empty.chunk_count(), 0);
+    }
+}
@@ -0,0 +1,473 @@
+use crate::{Hex, HexBounds, hex::ExactSizeHexIterator};
+#[cfg(feature = "bevy_platform")]
+use bevy_platform::collections::HashMap;
+#[cfg(not(feature = "bevy_platform"))]
+use std::collections::HashMap;
+use std::{fmt, sync::Arc};
+
+use super::{HexModMap, HexStore, HexStoreMap, HexStoreMut};
+
+/// Dense hexagonal chunk of a [`ChunkedMap`], with `None` for vacant
+/// coordinates
+pub type MapChunk<T> = HexModMap<Option<T>>;
+
+type LoadFn<T> = dyn Fn(Hex, &mut MapChunk<T>) + Send + Sync;
+type UnloadFn<T> = dyn Fn(Hex, &MapChunk<T>) + Send + Sync;
+
+/// Sparse storage for unbounded maps, lazily allocating dense hexagonal
+/// chunks.
+///
+/// Coordinates are grouped in hexagonal chunks of a given radius using
+/// [`Hex::to_lower_res`], every chunk being stored as a dense
+/// [`MapChunk`] allocated on the first insertion in its area. This fits
+/// streaming open worlds, where coordinates are added and removed as the
+/// player moves, while keeping fast accessors.
+///
+/// Chunk load and unload callbacks can be used to fill new chunks or to
+/// persist the unloaded ones:
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::*};
+/// let mut map = ChunkedMap::new(10).with_on_load(|chunk, values| {
+///     // Generate the chunk terrain
+///     for (coord, value) in values.iter_mut() {
+///         *value = Some(coord.x);
+///     }
+/// });
+/// // The first insertion allocates the chunk
+/// map.insert(hex(100, 35), 0).ok();
+/// assert_eq!(map.chunk_count(), 1);
+/// assert_eq!(map.len(), Hex::range_count(10) as usize);
+/// assert_eq!(map[hex(101, 34)], 101);
+/// // Unloading the chunk removes its values
+/// map.unload_chunk(map.chunk_coord(hex(100, 35)));
+/// assert!(map.is_empty());
+/// ```
+pub struct ChunkedMap<T> {
+    chunks: HashMap<Hex, MapChunk<T>>,
+    chunk_radius: u32,
+    len: usize,
+    on_load: Option<Arc<LoadFn<T>>>,
+    on_unload: Option<Arc<UnloadFn<T>>>,
+}
+
+impl<T> ChunkedMap<T> {
+    /// Creates an empty map with hexagonal chunks of the given `chunk_radius`
+    #[must_use]
+    pub fn new(chunk_radius: u32) -> Self {
+        Self {
+            chunks: HashMap::default(),
+            chunk_radius,
+            len: 0,
+            on_load: None,
+            on_unload: None,
+        }
+    }
+
+    /// Specifies a callback called on every chunk allocation, with the chunk
+    /// coordinate and its values, all vacant.
+    ///
+    /// The callback may fill the chunk, for example with generated or saved
+    /// data
+    #[must_use]
+    pub fn with_on_load(
+        mut self,
+        on_load: impl Fn(Hex, &mut MapChunk<T>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_load = Some(Arc::new(on_load));
+        self
+    }
+
+    /// Specifies a callback called before every chunk deallocation, with the
+    /// chunk coordinate and its values
+    #[must_use]
+    pub fn with_on_unload(
+        mut self,
+        on_unload: impl Fn(Hex, &MapChunk<T>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_unload = Some(Arc::new(on_unload));
+        self
+    }
+
+    /// Returns the radius of the chunks
+    #[inline]
+    #[must_use]
+    pub const fn chunk_radius(&self) -> u32 {
+        self.chunk_radius
+    }
+
+    /// Returns the amount of stored values
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no value is stored
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the amount of allocated chunks
+    #[inline]
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the coordinate of the chunk containing `hex`, in the lower
+    /// resolution coordinate system of the chunks
+    ///
+    /// > See [`Hex::to_lower_res`]
+    #[inline]
+    #[must_use]
+    pub fn chunk_coord(&self, hex: Hex) -> Hex {
+        hex.to_lower_res(self.chunk_radius)
+    }
+
+    /// Returns the coordinate bounds of the chunk at `chunk`
+    #[inline]
+    #[must_use]
+    pub const fn chunk_bounds(&self, chunk: Hex) -> HexBounds {
+        HexBounds::new(chunk.to_higher_res(self.chunk_radius), self.chunk_radius)
+    }
+
+    /// Returns the chunk at `chunk` if allocated
+    #[inline]
+    #[must_use]
+    pub fn chunk(&self, chunk: Hex) -> Option<&MapChunk<T>> {
+        self.chunks.get(&chunk)
+    }
+
+    /// Returns `true` if the chunk at `chunk` is allocated
+    #[inline]
+    #[must_use]
+    pub fn is_chunk_loaded(&self, chunk: Hex) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    /// An iterator visiting all allocated chunks with their coordinates, in
+    /// arbitrary order
+    #[must_use]
+    pub fn chunks(&self) -> impl ExactSizeIterator<Item = (Hex, &MapChunk<T>)> {
+        self.chunks.iter().map(|(c, chunk)| (*c, chunk))
+    }
+
+    /// An iterator visiting all stored key-value pairs of the chunk at
+    /// `chunk`, with mutable references to the values
+    pub fn chunk_iter_mut(&mut self, chunk: Hex) -> impl Iterator<Item = (Hex, &mut T)> {
+        self.chunks
+            .get_mut(&chunk)
+            .into_iter()
+            .flat_map(HexStore::iter_mut)
+            .filter_map(|(h, v)| Some((h, v.as_mut()?)))
+    }
+
+    /// Allocates the chunk at `chunk` if needed, calling the load callback
+    /// (See [`Self::with_on_load`])
+    pub fn load_chunk(&mut self, chunk: Hex) -> &MapChunk<T> {
+        self.load_chunk_mut(chunk)
+    }
+
+    fn load_chunk_mut(&mut self, chunk: Hex) -> &mut MapChunk<T> {
+        let center = chunk.to_higher_res(self.chunk_radius);
+        self.chunks.entry(chunk).or_insert_with_key(|&chunk| {
+            let mut values = HexModMap::new(center, self.chunk_radius, |_| None);
+            if let Some(on_load) = &self.on_load {
+                on_load(chunk, &mut values);
+                self.len += values.values().filter(|v| v.is_some()).count();
+            }
+            values
+        })
+    }
+
+    /// Deallocates the chunk at `chunk`, calling the unload callback (See
+    /// [`Self::with_on_unload`]) and returning its values.
+    ///
+    /// Returns `None` if the chunk is not allocated
+    pub fn unload_chunk(&mut self, chunk: Hex) -> Option<MapChunk<T>> {
+        let values = self.chunks.remove(&chunk)?;
+        self.on_unloaded(chunk, &values);
+        Some(values)
+    }
+
+    /// Retains only the chunks specified by the predicate, unloading the
+    /// others (See [`Self::unload_chunk`])
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let mut map = ChunkedMap::new(5);
+    /// for coord in Hex::ZERO.range(30) {
+    ///     map.insert(coord, ()).ok();
+    /// }
+    /// // Stream out the chunks far from the player
+    /// let player = map.chunk_coord(hex(15, -5));
+    /// map.retain_chunks(|chunk| chunk.distance_to(player) <= 1);
+    /// assert_eq!(map.chunk_count(), 7);
+    /// ```
+    pub fn retain_chunks(&mut self, mut f: impl FnMut(Hex) -> bool) {
+        let unloaded: Vec<_> = self.chunks.keys().copied().filter(|c| !f(*c)).collect();
+        for chunk in unloaded {
+            self.unload_chunk(chunk);
+        }
+    }
+
+    /// Unloads every chunk (See [`Self::unload_chunk`])
+    pub fn clear(&mut self) {
+        self.retain_chunks(|_| false);
+    }
+
+    fn on_unloaded(&mut self, chunk: Hex, values: &MapChunk<T>) {
+        self.len -= values.values().filter(|v| v.is_some()).count();
+        if let Some(on_unload) = &self.on_unload {
+            on_unload(chunk, values);
+        }
+    }
+
+    /// Returns the value slot of `hex`, allocating its chunk if needed
+    fn slot(&mut self, hex: Hex) -> &mut Option<T> {
+        &mut self.load_chunk_mut(self.chunk_coord(hex))[hex]
+    }
+
+    /// Returns a mutable reference to the value at `hex`, inserting the result
+    /// of `f` if vacant
+    pub fn get_or_insert_with(&mut self, hex: Hex, f: impl FnOnce() -> T) -> &mut T {
+        self.load_chunk_mut(self.chunk_coord(hex));
+        if self.get(hex).is_none() {
+            self.len += 1;
+        }
+        self.slot(hex).get_or_insert_with(f)
+    }
+}
+
+impl<T> HexStore<T> for ChunkedMap<T> {
+    fn get(&self, hex: Hex) -> Option<&T> {
+        self.chunks.get(&self.chunk_coord(hex))?.get(hex)?.as_ref()
+    }
+
+    fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        let chunk = self.chunk_coord(hex);
+        self.chunks.get_mut(&chunk)?.get_mut(hex)?.as_mut()
+    }
+
+    fn values<'s>(&'s self) -> impl ExactSizeIterator<Item = &'s T>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self.chunks.values().flat_map(|c| c.values().flatten()),
+        }
+    }
+
+    fn values_mut<'s>(&'s mut self) -> impl ExactSizeIterator<Item = &'s mut T>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self
+                .chunks
+                .values_mut()
+                .flat_map(|c| c.values_mut().flatten()),
+        }
+    }
+
+    fn iter<'s>(&'s self) -> impl ExactSizeIterator<Item = (Hex, &'s T)>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self
+                .chunks
+                .values()
+                .flat_map(|c| c.iter().filter_map(|(h, v)| Some((h, v.as_ref()?)))),
+        }
+    }
+
+    fn iter_mut<'s>(&'s mut self) -> impl ExactSizeIterator<Item = (Hex, &'s mut T)>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self
+                .chunks
+                .values_mut()
+                .flat_map(|c| c.iter_mut().filter_map(|(h, v)| Some((h, v.as_mut()?)))),
+        }
+    }
+}
+
+impl<T> HexStoreMut<T> for ChunkedMap<T> {
+    #[inline]
+    fn can_insert(&self, _hex: Hex) -> bool {
+        true
+    }
+
+    /// Inserts `value` at `hex`, allocating its chunk if needed, and returns
+    /// the previous value. Never fails
+    fn insert(&mut self, hex: Hex, value: T) -> Result<Option<T>, T> {
+        let previous = self.slot(hex).replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        Ok(previous)
+    }
+
+    /// Removes the value at `hex` and returns it. Chunks are not deallocated
+    /// (See [`ChunkedMap::unload_chunk`])
+    fn remove(&mut self, hex: Hex) -> Option<T> {
+        let chunk = self.chunks.get_mut(&self.chunk_coord(hex))?;
+        let value = chunk.get_mut(hex)?.take()?;
+        self.len -= 1;
+        Some(value)
+    }
+}
+
+impl<T> HexStoreMap<T> for ChunkedMap<T> {
+    type Mapped<U> = ChunkedMap<U>;
+
+    /// Maps the values to a new [`ChunkedMap`] with the same chunks, without
+    /// the load and unload callbacks
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|(c, chunk)| {
+                let chunk = chunk.map_values(|h, v| v.as_ref().map(|v| f(h, v)));
+                (*c, chunk)
+            })
+            .collect();
+        ChunkedMap {
+            chunks,
+            chunk_radius: self.chunk_radius,
+            len: self.len,
+            on_load: None,
+            on_unload: None,
+        }
+    }
+}
+
+impl<T> Extend<(Hex, T)> for ChunkedMap<T> {
+    fn extend<I: IntoIterator<Item = (Hex, T)>>(&mut self, iter: I) {
+        for (hex, value) in iter {
+            let _ = HexStoreMut::insert(self, hex, value);
+        }
+    }
+}
+
+impl<T> fmt::Debug for ChunkedMap<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedMap")
+            .field("chunks", &self.chunks)
+            .field("chunk_radius", &self.chunk_radius)
+            .field("len", &self.len)
+            .field("on_load", &self.on_load.is_some())
+            .field("on_unload", &self.on_unload.is_some())
+            .finish()
+    }
+}
+
+impl<T> Clone for ChunkedMap<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            chunks: self.chunks.clone(),
+            chunk_radius: self.chunk_radius,
+            len: self.len,
+            on_load: self.on_load.clone(),
+            on_unload: self.on_unload.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn chunk_bounds() {
+        for radius in 0..6 {
+            let map = ChunkedMap::<()>::new(radius);
+            for coord in Hex::ZERO.range(20) {
+                let chunk = map.chunk_coord(coord);
+                assert!(map.chunk_bounds(chunk).is_in_bounds(coord));
+            }
+        }
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut map = ChunkedMap::new(3);
+        let mut expected = HashMap::new();
+        for (i, coord) in Hex::ZERO.range(15).enumerate() {
+            if i % 3 == 0 {
+                assert_eq!(map.insert(coord, i), Ok(None));
+                expected.insert(coord, i);
+            }
+        }
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map.iter().len(), expected.len());
+        let iter: HashMap<Hex, usize> = map.iter().map(|(k, v)| (k, *v)).collect();
+        assert_eq!(iter, expected);
+        for coord in Hex::ZERO.range(16) {
+            assert_eq!(map.get(coord), expected.get(&coord));
+        }
+        for (coord, i) in &expected {
+            assert_eq!(map.insert(*coord, i + 1), Ok(Some(*i)));
+            assert_eq!(map.remove(*coord), Some(i + 1));
+            assert_eq!(map.remove(*coord), None);
+        }
+        assert!(map.is_empty());
+        assert!(map.chunk_count() > 0);
+        map.clear();
+        assert_eq!(map.chunk_count(), 0);
+    }
+
+    #[test]
+    fn callbacks() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static UNLOADED: AtomicUsize = AtomicUsize::new(0);
+        let mut map = ChunkedMap::new(2)
+            .with_on_load(|chunk, values| {
+                if let Some(center) = values.get_mut(chunk.to_higher_res(2)) {
+                    *center = Some(chunk);
+                }
+            })
+            .with_on_unload(|_, values| {
+                let count = values.values().flatten().count();
+                UNLOADED.fetch_add(count, Ordering::Relaxed);
+            });
+        // Next to the chunk centers
+        map.insert(hex(0, 1), Hex::ZERO).ok();
+        map.insert(Hex::X.to_higher_res(2) + hex(0, 1), Hex::ZERO)
+            .ok();
+        assert_eq!(map.chunk_count(), 2);
+        assert_eq!(map.len(), 4);
+        for (chunk, values) in map.chunks() {
+            assert_eq!(values.get(chunk.to_higher_res(2)), Some(&Some(chunk)));
+        }
+        let kept = map.chunk_coord(hex(0, 1));
+        map.retain_chunks(|c| c == kept);
+        assert_eq!(map.len(), 2);
+        assert_eq!(UNLOADED.load(Ordering::Relaxed), 2);
+        assert_eq!(*map.get_or_insert_with(hex(0, 1), || Hex::ONE), Hex::ZERO);
+        assert_eq!(*map.get_or_insert_with(hex(1, 0), || Hex::ONE), Hex::ONE);
+        assert_eq!(map.len(), 3);
+    }
+}
@@ -3,7 +3,7 @@ use crate::{Hex, HexBounds, hex::ExactSizeHexIterator};
 use rayon::prelude::*;
 use std::fmt;
 
-use super::HexStore;
+use super::{HexStore, HexStoreMap, HexStoreStep, disjoint_mut};
 
 /// [`Vec`] Based storage for hexagonal maps.
 ///
@@ -69,6 +69,36 @@ impl HexagonalMapMetadata {
 
         Hex { x, y } - self.offset()
     }
+
+    /// Returns the indices of the neighbors of the coordinate at `[y, x]`,
+    /// in [`EdgeDirection`](crate::EdgeDirection) order, without converting
+    /// back to coordinates
+    #[expect(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    fn neighbor_indices(&self, [y, x]: [usize; 2]) -> [Option<[usize; 2]>; 6] {
+        let radius = self.bounds.radius as isize;
+        // Amount of skipped coordinates at the start of a row
+        let shift = |y: isize| (radius - y).max(0);
+        let [y, x] = [y as isize, x as isize];
+        Hex::NEIGHBORS_COORDS.map(|dir| {
+            let ny = y + dir.y as isize;
+            if !(0..=2 * radius).contains(&ny) {
+                return None;
+            }
+            let nx = x + dir.x as isize + shift(y) - shift(ny);
+            let row_len = 2 * radius + 1 - (radius - ny).abs();
+            (0..row_len)
+                .contains(&nx)
+                .then_some([ny as usize, nx as usize])
+        })
+    }
+
+    /// Returns the indices of the neighbors of `hex`
+    fn hex_neighbor_indices(&self, hex: Hex) -> [Option<[usize; 2]>; 6] {
+        self.hex_to_idx(hex).map_or_else(
+            || hex.all_neighbors().map(|n| self.hex_to_idx(n)),
+            |idx| self.neighbor_indices(idx),
+        )
+    }
 }
 
 impl<T> HexagonalMap<T> {
@@ -175,6 +205,93 @@ impl<T> HexagonalMap<T> {
     pub const fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
+
+    /// Returns mutable references to the values of the 6 neighbors of `hex`,
+    /// in [`EdgeDirection`](crate::EdgeDirection) order.
+    /// Out of bounds neighbors are `None`
+    ///
+    /// > See [`HexStore::neighbors`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let mut map = HexagonalMap::new(Hex::ZERO, 5, |_| 0);
+    /// for value in map.neighbors_mut(hex(1, 0)).into_iter().flatten() {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(map[Hex::ZERO], 1);
+    /// assert_eq!(map[hex(1, 0)], 0);
+    /// ```
+    #[must_use]
+    pub fn neighbors_mut(&mut self, hex: Hex) -> [Option<&mut T>; 6] {
+        let indices = self.meta.hex_neighbor_indices(hex);
+        let mut res = [const { None }; 6];
+        // Neighbors span over 3 consecutive rows
+        let Some(first_row) = indices.iter().flatten().map(|[y, _]| *y).min() else {
+            return res;
+        };
+        let rows = disjoint_mut(&mut self.inner, [0, 1, 2].map(|i| Some(first_row + i)));
+        for (row_idx, row) in (first_row..).zip(rows) {
+            let Some(row) = row else {
+                continue;
+            };
+            let columns = indices.map(|idx| {
+                let [y, x] = idx?;
+                (y == row_idx).then_some(x)
+            });
+            for (slot, value) in res.iter_mut().zip(disjoint_mut(row, columns)) {
+                if value.is_some() {
+                    *slot = value;
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns the values of the neighbors of the coordinate at `[y, x]`
+    fn neighbors_at(&self, idx: [usize; 2]) -> [Option<&T>; 6] {
+        self.meta
+            .neighbor_indices(idx)
+            .map(|idx| idx.map(|[y, x]| &self.inner[y][x]))
+    }
+
+    /// Returns the `[row, column]` index of `hex`, if in bounds
+    pub(crate) fn index_of(&self, hex: Hex) -> Option<[usize; 2]> {
+        self.meta.hex_to_idx(hex)
+    }
+
+    /// Returns the coordinate at the `[row, column]` index
+    pub(crate) fn coord_at(&self, idx: [usize; 2]) -> Hex {
+        self.meta.idx_to_hex(idx)
+    }
+
+    /// Returns the length of every row
+    pub(crate) fn row_lens(&self) -> impl ExactSizeIterator<Item = usize> {
+        self.inner.iter().map(Vec::len)
+    }
+}
+
+impl<T> HexStoreMap<T> for HexagonalMap<T> {
+    type Mapped<U> = HexagonalMap<U>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        let inner = self
+            .inner
+            .iter()
+            .enumerate()
+            .map(|(y, arr)| {
+                arr.iter()
+                    .enumerate()
+                    .map(|(x, value)| f(self.meta.idx_to_hex([y, x]), value))
+                    .collect()
+            })
+            .collect();
+        HexagonalMap {
+            inner,
+            meta: self.meta,
+        }
+    }
 }
 
 impl<T> HexStore<T> for HexagonalMap<T> {
@@ -236,6 +353,65 @@ impl<T> HexStore<T> for HexagonalMap<T> {
         });
         ExactSizeHexIterator { iter, count }
     }
+
+    fn neighbors(&self, hex: Hex) -> [Option<&T>; 6] {
+        self.meta
+            .hex_neighbor_indices(hex)
+            .map(|idx| idx.map(|[y, x]| &self.inner[y][x]))
+    }
+
+    fn iter_with_neighbors<'s>(
+        &'s self,
+    ) -> impl ExactSizeIterator<Item = (Hex, &'s T, [Option<&'s T>; 6])>
+    where
+        T: 's,
+    {
+        let count = self.len();
+        let iter = self.inner.iter().enumerate().flat_map(move |(y, arr)| {
+            arr.iter().enumerate().map(move |(x, value)| {
+                let hex = self.meta.idx_to_hex([y, x]);
+                (hex, value, self.neighbors_at([y, x]))
+            })
+        });
+        ExactSizeHexIterator { iter, count }
+    }
+}
+
+impl<T> HexStoreStep<T> for HexagonalMap<T> {
+    fn step_into(&self, buffer: &mut Self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T) {
+        buffer.meta = self.meta;
+        buffer.inner.resize_with(self.inner.len(), Vec::new);
+        for (y, (arr, buffer_arr)) in self.inner.iter().zip(&mut buffer.inner).enumerate() {
+            buffer_arr.clear();
+            buffer_arr.extend(arr.iter().enumerate().map(|(x, value)| {
+                let hex = self.meta.idx_to_hex([y, x]);
+                rule(hex, value, self.neighbors_at([y, x]))
+            }));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn step_into_parallel(
+        &self,
+        buffer: &mut Self,
+        rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T + Sync,
+    ) where
+        T: Send + Sync,
+    {
+        buffer.meta = self.meta;
+        buffer.inner.resize_with(self.inner.len(), Vec::new);
+        self.inner
+            .par_iter()
+            .zip(&mut buffer.inner)
+            .enumerate()
+            .for_each(|(y, (arr, buffer_arr))| {
+                buffer_arr.clear();
+                buffer_arr.extend(arr.iter().enumerate().map(|(x, value)| {
+                    let hex = self.meta.idx_to_hex([y, x]);
+                    rule(hex, value, self.neighbors_at([y, x]))
+                }));
+            });
+    }
 }
 
 impl<T> fmt::Debug for HexagonalMap<T>
@@ -313,4 +489,24 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn neighbors() {
+        for radius in 0_u32..8 {
+            let center = Hex::new(-2, 5);
+            let mut map = HexagonalMap::new(center, radius, |h| h);
+            assert_eq!(map.iter_with_neighbors().len(), map.len());
+            for (hex, value, neighbors) in map.iter_with_neighbors() {
+                assert_eq!(hex, *value);
+                let expected = hex.all_neighbors().map(|n| map.get(n));
+                assert_eq!(neighbors, expected);
+            }
+            for hex in center.range(radius + 2) {
+                let expected = hex.all_neighbors().map(|n| map.get(n).copied());
+                assert_eq!(map.neighbors(hex).map(Option::<&Hex>::copied), expected);
+                let neighbors_mut = map.neighbors_mut(hex).map(Option::<&mut Hex>::copied);
+                assert_eq!(neighbors_mut, expected);
+            }
+        }
+    }
 }
@@ -3,7 +3,7 @@ use crate::{Hex, HexBounds, hex::ExactSizeHexIterator};
 use rayon::prelude::*;
 use std::fmt;
 
-use super::HexStore;
+use super::{HexStore, HexStoreMap, HexStoreStep};
 
 /// [`Vec`] Based storage for hexagonal maps using [hexmod] coordinates.
 ///
@@ -168,6 +168,47 @@ impl<T> HexModMap<T> {
     }
 }
 
+impl<T> HexStoreStep<T> for HexModMap<T> {
+    fn step_into(&self, buffer: &mut Self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T) {
+        buffer.meta = self.meta;
+        buffer.inner.clear();
+        buffer.inner.extend(
+            self.iter_with_neighbors()
+                .map(|(hex, value, neighbors)| rule(hex, value, neighbors)),
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    fn step_into_parallel(
+        &self,
+        buffer: &mut Self,
+        rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T + Sync,
+    ) where
+        T: Send + Sync,
+    {
+        buffer.meta = self.meta;
+        self.inner
+            .par_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let hex = self.meta.idx_to_hex(idx);
+                rule(hex, value, self.neighbors(hex))
+            })
+            .collect_into_vec(&mut buffer.inner);
+    }
+}
+
+impl<T> HexStoreMap<T> for HexModMap<T> {
+    type Mapped<U> = HexModMap<U>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        HexModMap {
+            inner: self.iter().map(|(hex, value)| f(hex, value)).collect(),
+            meta: self.meta,
+        }
+    }
+}
+
 impl<T> HexStore<T> for HexModMap<T> {
     fn get(&self, hex: crate::Hex) -> Option<&T> {
         let idx = self.meta.hex_to_idx(hex)?;
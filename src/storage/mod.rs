@@ -6,15 +6,25 @@
 //! * [`RombusMap`] is up to ~100x faster than a hash map
 //!
 //! [this article]: https://www.redblobgames.com/grids/hexagons/#map-storage
+pub(crate) mod automaton;
+pub(crate) mod bitset;
+pub(crate) mod chunked;
 pub(crate) mod hexagonal;
 pub(crate) mod hexmod;
 pub(crate) mod rect;
 pub(crate) mod rombus;
+pub(crate) mod sparse;
 
+pub use automaton::CellularAutomaton;
+pub use bitset::{ChunkedHexBitSet, HexBitSet};
+pub use chunked::{ChunkedMap, MapChunk};
 pub use hexagonal::HexagonalMap;
 pub use hexmod::HexModMap;
 pub use rect::{RectMap, RectMetadata, WrapStrategy};
 pub use rombus::RombusMap;
+pub use sparse::SparseHexagonalMap;
+
+use crate::Hex;
 
 macro_rules! storage_impl {
     ($ty:ty) => {
@@ -52,6 +62,8 @@ storage_impl!(HexagonalMap<T>);
 storage_impl!(RombusMap<T>);
 storage_impl!(HexModMap<T>);
 storage_impl!(RectMap<T>);
+storage_impl!(ChunkedMap<T>);
+storage_impl!(SparseHexagonalMap<T>);
 
 /// Trait grouping common features for hexagonal storage types.
 ///
@@ -101,6 +113,329 @@ pub trait HexStore<T> {
     fn iter_mut<'s>(&'s mut self) -> impl ExactSizeIterator<Item = (crate::Hex, &'s mut T)>
     where
         T: 's;
+
+    /// Returns the stored coordinate matching `hex`, taking wrapping in
+    /// account.
+    /// Returns `None` if `hex` is out of bounds
+    ///
+    /// By default no wrapping is applied, but [`RectMap`] wraps coordinates
+    /// along its [`WrapStrategy::Cycle`] axes
+    /// (See [`RectMetadata::cycle_hex`])
+    #[must_use]
+    fn wrapped_coord(&self, hex: crate::Hex) -> Option<crate::Hex> {
+        self.get(hex).is_some().then_some(hex)
+    }
+
+    /// Returns references to the values of the 6 neighbors of `hex`, in
+    /// [`EdgeDirection`](crate::EdgeDirection) order.
+    /// Out of bounds neighbors are `None`
+    ///
+    /// [`RectMap`] wraps neighbors along its [`WrapStrategy::Cycle`] axes,
+    /// and the dense storages use index arithmetic
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let map = HexagonalMap::new(Hex::ZERO, 2, |coord| coord.ulength());
+    /// let neighbors = map.neighbors(hex(2, 0));
+    /// let south_west = EdgeDirection::FLAT_SOUTH_WEST.index() as usize;
+    /// assert_eq!(neighbors[south_west], Some(&2));
+    /// assert_eq!(neighbors.iter().flatten().count(), 3);
+    /// ```
+    #[must_use]
+    fn neighbors(&self, hex: crate::Hex) -> [Option<&T>; 6] {
+        hex.all_neighbors().map(|n| self.get(n))
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, with
+    /// the values of their neighbors (See [`Self::neighbors`]).
+    /// The iterator element type is `(Hex, &'s T, [Option<&'s T>; 6])`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let map = HexagonalMap::new(Hex::ZERO, 5, |coord| coord.ulength());
+    /// // Local maxima
+    /// let peaks = map
+    ///     .iter_with_neighbors()
+    ///     .filter(|(_, v, neighbors)| neighbors.iter().flatten().all(|n| *n <= *v))
+    ///     .count();
+    /// assert_eq!(peaks, Hex::ring_count(5));
+    /// ```
+    fn iter_with_neighbors<'s>(
+        &'s self,
+    ) -> impl ExactSizeIterator<Item = (crate::Hex, &'s T, [Option<&'s T>; 6])>
+    where
+        T: 's,
+    {
+        self.iter()
+            .map(|(hex, value)| (hex, value, self.neighbors(hex)))
+    }
+}
+
+/// Returns disjoint mutable references to the elements of `slice` at
+/// `indices`.
+///
+/// Out of range or repeated indices are `None`, only the first occurrence of
+/// an index is returned
+pub(crate) fn disjoint_mut<T, const N: usize>(
+    mut slice: &mut [T],
+    indices: [Option<usize>; N],
+) -> [Option<&mut T>; N] {
+    let mut order: [usize; N] = std::array::from_fn(|i| i);
+    order.sort_unstable_by_key(|i| (indices[*i], *i));
+    let mut res = [const { None }; N];
+    // Index of the first element of `slice`
+    let mut offset = 0;
+    for i in order {
+        let Some(index) = indices[i].filter(|index| *index >= offset) else {
+            continue;
+        };
+        let Some((value, rest)) = std::mem::take(&mut slice)
+            .get_mut(index - offset..)
+            .and_then(<[T]>::split_first_mut)
+        else {
+            break;
+        };
+        res[i] = Some(value);
+        slice = rest;
+        offset = index + 1;
+    }
+    res
+}
+
+/// Hexagonal storage which can be mapped to a storage of the same kind, with
+/// the same coordinates but different values.
+///
+/// Implemented for
+/// - [`HexagonalMap<T>`](HexagonalMap)
+/// - [`HexModMap<T>`](HexModMap)
+/// - [`RombusMap<T>`](RombusMap)
+/// - [`RectMap<T>`](RectMap)
+/// - [`ChunkedMap<T>`](ChunkedMap)
+/// - [`SparseHexagonalMap<T>`](SparseHexagonalMap)
+#[cfg_attr(
+    not(feature = "bevy_platform"),
+    doc = "- [`HashMap<Hex, T>`](std::collections::HashMap)"
+)]
+#[cfg_attr(
+    feature = "bevy_platform",
+    doc = "- [`HashMap<Hex, T>`](bevy_platform::collections::HashMap)"
+)]
+pub trait HexStoreMap<T>: HexStore<T> {
+    /// Storage of the same kind, storing `U` values
+    type Mapped<U>: HexStoreMap<U>;
+
+    /// Creates a storage of the same kind and coordinates, with values
+    /// computed by `f` for every coordinate and value of `self`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let map = HexagonalMap::new(Hex::ZERO, 5, |coord| coord.ulength());
+    /// let even = map.map_values(|_, length| length % 2 == 0);
+    /// assert_eq!(even[hex(1, 1)], true);
+    /// ```
+    #[must_use]
+    fn map_values<U>(&self, f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U>;
+}
+
+/// Dense hexagonal storage computing the generations of cellular automata,
+/// like fire spread, fluid or erosion simulations.
+///
+/// > See [`CellularAutomaton`] for a double-buffered stepper
+///
+/// Implemented for
+/// - [`HexagonalMap<T>`](HexagonalMap)
+/// - [`HexModMap<T>`](HexModMap)
+/// - [`RombusMap<T>`](RombusMap)
+/// - [`RectMap<T>`](RectMap)
+pub trait HexStoreStep<T>: HexStore<T> {
+    /// Overwrites `buffer` with the next generation of `self`, computed by
+    /// `rule` for every coordinate from its value and the values of its
+    /// neighbors (See [`HexStore::neighbors`]).
+    ///
+    /// `buffer` takes the layout of `self`, reusing its allocations
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let map = HexagonalMap::new(Hex::ZERO, 5, |coord| coord == Hex::ZERO);
+    /// let mut buffer = map.clone();
+    /// // Spreads to the neighbors
+    /// map.step_into(&mut buffer, |_, v, neighbors| {
+    ///     *v || neighbors.contains(&Some(&true))
+    /// });
+    /// assert!(buffer[hex(1, 0)]);
+    /// assert!(!buffer[hex(2, 0)]);
+    /// ```
+    fn step_into(&self, buffer: &mut Self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T);
+
+    /// Overwrites `buffer` with the next generation of `self` using parallel
+    /// processing with `rayon`
+    ///
+    /// > See [`Self::step_into`]
+    #[cfg(feature = "rayon")]
+    fn step_into_parallel(
+        &self,
+        buffer: &mut Self,
+        rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T + Sync,
+    ) where
+        T: Send + Sync;
+}
+
+/// Hexagonal storage supporting insertion and removal of values, allowing
+/// algorithms to be written once for sparse and dense storages.
+///
+/// Implemented for
+/// - [`SparseHexagonalMap<T>`](SparseHexagonalMap)
+/// - [`ChunkedMap<T>`](ChunkedMap)
+#[cfg_attr(
+    not(feature = "bevy_platform"),
+    doc = "- [`HashMap<Hex, T>`](std::collections::HashMap)"
+)]
+#[cfg_attr(
+    feature = "bevy_platform",
+    doc = "- [`HashMap<Hex, T>`](bevy_platform::collections::HashMap)"
+)]
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::*};
+/// # use std::collections::HashMap;
+/// fn count_visits(store: &mut impl HexStoreMut<u32>, path: &[Hex]) {
+///     for coord in path {
+///         if let Some(entry) = store.entry(*coord) {
+///             *entry.or_default() += 1;
+///         }
+///     }
+/// }
+///
+/// let path = [hex(0, 0), hex(1, 0), hex(0, 0), hex(20, 0)];
+/// let mut map = HashMap::new();
+/// count_visits(&mut map, &path);
+/// assert_eq!(map.len(), 3);
+/// let mut sparse = SparseHexagonalMap::new(Hex::ZERO, 10);
+/// count_visits(&mut sparse, &path);
+/// // The last coordinate is out of bounds
+/// assert_eq!(sparse.len(), 2);
+/// assert_eq!(sparse[hex(0, 0)], 2);
+/// ```
+pub trait HexStoreMut<T>: HexStore<T> {
+    /// Returns `true` if a value can be stored at `hex`, meaning `hex` is in
+    /// the storage bounds
+    #[must_use]
+    fn can_insert(&self, hex: Hex) -> bool;
+
+    /// Inserts `value` at `hex` and returns the previously stored value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` back if it can't be stored, if `hex` is out of bounds
+    /// (See [`Self::can_insert`])
+    fn insert(&mut self, hex: Hex, value: T) -> Result<Option<T>, T>;
+
+    /// Removes and returns the value stored at `hex`, if any
+    fn remove(&mut self, hex: Hex) -> Option<T>;
+
+    /// Returns the [`HexEntry`] of `hex` for in-place manipulation.
+    /// Returns `None` if `hex` is out of bounds (See [`Self::can_insert`])
+    #[must_use]
+    fn entry(&mut self, hex: Hex) -> Option<HexEntry<'_, T, Self>>
+    where
+        Self: Sized,
+    {
+        self.can_insert(hex).then_some(HexEntry {
+            store: self,
+            hex,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+/// Entry of a coordinate in a [`HexStoreMut`] storage, which may be vacant or
+/// occupied.
+///
+/// > See [`HexStoreMut::entry`]
+#[derive(Debug)]
+pub struct HexEntry<'s, T, S> {
+    store: &'s mut S,
+    hex: Hex,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<'s, T, S: HexStoreMut<T>> HexEntry<'s, T, S> {
+    /// Returns the entry coordinate
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> Hex {
+        self.hex
+    }
+
+    /// Returns a reference to the stored value, if any
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> Option<&T> {
+        self.store.get(self.hex)
+    }
+
+    /// Calls `f` on the stored value, if any
+    #[must_use]
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(value) = self.store.get_mut(self.hex) {
+            f(value);
+        }
+        self
+    }
+
+    /// Inserts the result of `f` if the entry is vacant, and returns a mutable
+    /// reference to the stored value
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the storage doesn't store the value, breaking the
+    /// [`HexStoreMut::can_insert`] contract
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'s mut T {
+        if self.store.get(self.hex).is_none() {
+            // Entries are only created for coordinates in bounds
+            let _ = self.store.insert(self.hex, f());
+        }
+        self.store
+            .get_mut(self.hex)
+            .expect("Entry coordinate is in bounds")
+    }
+
+    /// Inserts `value` if the entry is vacant, and returns a mutable
+    /// reference to the stored value
+    pub fn or_insert(self, value: T) -> &'s mut T {
+        self.or_insert_with(|| value)
+    }
+
+    /// Inserts the default value if the entry is vacant, and returns a mutable
+    /// reference to the stored value
+    pub fn or_default(self) -> &'s mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+}
+
+impl<T, S: std::hash::BuildHasher + Clone> HexStoreMap<T>
+    for std::collections::HashMap<crate::Hex, T, S>
+{
+    type Mapped<U> = std::collections::HashMap<crate::Hex, U, S>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        let mut res = Self::Mapped::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        res.extend(self.iter().map(|(k, v)| (*k, f(*k, v))));
+        res
+    }
 }
 
 impl<T, S: std::hash::BuildHasher> HexStore<T> for std::collections::HashMap<crate::Hex, T, S> {
@@ -147,6 +482,36 @@ impl<T, S: std::hash::BuildHasher> HexStore<T> for std::collections::HashMap<cra
     }
 }
 
+impl<T, S: std::hash::BuildHasher> HexStoreMut<T> for std::collections::HashMap<crate::Hex, T, S> {
+    #[inline]
+    fn can_insert(&self, _hex: crate::Hex) -> bool {
+        true
+    }
+
+    #[inline]
+    fn insert(&mut self, hex: crate::Hex, value: T) -> Result<Option<T>, T> {
+        Ok(self.insert(hex, value))
+    }
+
+    #[inline]
+    fn remove(&mut self, hex: crate::Hex) -> Option<T> {
+        self.remove(&hex)
+    }
+}
+
+#[cfg(feature = "bevy_platform")]
+impl<T, S: core::hash::BuildHasher + Clone> HexStoreMap<T>
+    for bevy_platform::collections::HashMap<crate::Hex, T, S>
+{
+    type Mapped<U> = bevy_platform::collections::HashMap<crate::Hex, U, S>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        let mut res = Self::Mapped::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        res.extend(self.iter().map(|(k, v)| (*k, f(*k, v))));
+        res
+    }
+}
+
 #[cfg(feature = "bevy_platform")]
 impl<T, S: core::hash::BuildHasher> HexStore<T>
     for bevy_platform::collections::HashMap<crate::Hex, T, S>
@@ -193,3 +558,23 @@ impl<T, S: core::hash::BuildHasher> HexStore<T>
         self.iter_mut().map(|(k, v)| (*k, v))
     }
 }
+
+#[cfg(feature = "bevy_platform")]
+impl<T, S: core::hash::BuildHasher> HexStoreMut<T>
+    for bevy_platform::collections::HashMap<crate::Hex, T, S>
+{
+    #[inline]
+    fn can_insert(&self, _hex: crate::Hex) -> bool {
+        true
+    }
+
+    #[inline]
+    fn insert(&mut self, hex: crate::Hex, value: T) -> Result<Option<T>, T> {
+        Ok(self.insert(hex, value))
+    }
+
+    #[inline]
+    fn remove(&mut self, hex: crate::Hex) -> Option<T> {
+        self.remove(&hex)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{Hex, HexOrientation, OffsetHexMode, storage::HexStore};
+use crate::{
+    Hex, HexOrientation, HexTopology, OffsetHexMode,
+    storage::{HexStore, HexStoreMap, HexStoreStep, disjoint_mut},
+};
 use glam::{IVec2, UVec2};
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -59,7 +62,7 @@ pub struct RectMap<T> {
 /// assert_eq!(rect_hex_map.get(hex), Some(&0_i32));
 /// assert_eq!(rect_hex_map.wrapped_get(hex), &0_i32);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
 pub struct RectMetadata {
@@ -314,7 +317,7 @@ impl RectMetadata {
     /// - => `rc` 2D view of `Vec`
     /// - => `ij` offset coordinate
     /// - => `hex`
-    fn idx_to_hex(&self, idx: usize) -> Hex {
+    pub(crate) fn idx_to_hex(&self, idx: usize) -> Hex {
         let ij = self.idx_to_ij(idx);
         self.ij_to_hex(ij)
     }
@@ -334,7 +337,7 @@ impl RectMetadata {
     // Backward Coordinate Conversion
     // ================================
     /// `None` if outside map
-    fn hex_to_idx(&self, hex: Hex) -> Option<usize> {
+    pub(crate) fn hex_to_idx(&self, hex: Hex) -> Option<usize> {
         let ij = self.hex_to_offset(hex);
         self.contains_offset(ij).then(|| self.ij_to_idx(ij))
     }
@@ -404,6 +407,84 @@ impl RectMetadata {
         self.ij_to_hex(ij)
     }
 
+    /// Wraps `hex` along the [`WrapStrategy::Cycle`] axes only, leaving
+    /// [`WrapStrategy::Clamp`] axes untouched.
+    ///
+    /// An odd dimension along the staggered offset axis (rows for
+    /// [`HexOrientation::Pointy`], columns for [`HexOrientation::Flat`]) can't
+    /// be wrapped seamlessly and is left untouched as well, keeping neighbors
+    /// symmetric.
+    ///
+    /// Returns `None` if the resulting coordinate is outside of the map
+    #[must_use]
+    pub fn cycle_hex(&self, hex: Hex) -> Option<Hex> {
+        self.cycle_offset(self.hex_to_offset(hex))
+            .map(|ij| self.ij_to_hex(ij))
+    }
+
+    /// Wraps `offset` along the seamless [`WrapStrategy::Cycle`] axes only
+    /// (See [`Self::seamless_cycles`])
+    fn cycle_offset(&self, offset: IVec2) -> Option<IVec2> {
+        self.cycle_offset_along(offset, self.seamless_cycles())
+    }
+
+    /// Wraps `offset` along the `axes` flagged as cycling only
+    fn cycle_offset_along(&self, offset: IVec2, axes: [bool; 2]) -> Option<IVec2> {
+        let wrapped = self.wrap_offset(offset);
+        let cycled = IVec2::new(
+            if axes[0] { wrapped.x } else { offset.x },
+            if axes[1] { wrapped.y } else { offset.y },
+        );
+        self.contains_offset(cycled).then_some(cycled)
+    }
+
+    /// [`WrapStrategy::Cycle`] axes which can be wrapped seamlessly.
+    ///
+    /// Offset coordinates are staggered along the rows for
+    /// [`HexOrientation::Pointy`] and along the columns for
+    /// [`HexOrientation::Flat`]. Cycling an odd amount of staggered lines
+    /// changes the parity of the coordinates, so the seam is not a hexagonal
+    /// translation and neighbors across it are not symmetric.
+    const fn seamless_cycles(&self) -> [bool; 2] {
+        let staggered = match self.orientation {
+            HexOrientation::Pointy => 1,
+            HexOrientation::Flat => 0,
+        };
+        let dim = self.dim.to_array();
+        let mut cycles = [false; 2];
+        let mut axis = 0;
+        while axis < 2 {
+            cycles[axis] = matches!(self.wrap_strategies[axis], WrapStrategy::Cycle)
+                && (axis != staggered || dim[axis].is_multiple_of(2));
+            axis += 1;
+        }
+        cycles
+    }
+
+    /// Index of `hex` wrapped along the seamless [`WrapStrategy::Cycle`] axes
+    fn cycle_hex_to_idx(&self, hex: Hex) -> Option<usize> {
+        self.cycle_offset(self.hex_to_offset(hex))
+            .map(|ij| self.ij_to_idx(ij))
+    }
+
+    /// Offset coordinate translations to the neighbors in
+    /// [`EdgeDirection`](crate::EdgeDirection) order, which only depend on the
+    /// parity of the offset coordinate (See [`Self::parity`])
+    fn neighbor_offsets(&self) -> [[IVec2; 6]; 4] {
+        std::array::from_fn(|parity| {
+            let ij = IVec2::new(i32::from(parity & 1 != 0), i32::from(parity & 2 != 0));
+            let hex = self.ij_to_hex(ij);
+            hex.all_neighbors()
+                .map(|neighbor| self.hex_to_offset(neighbor) - ij)
+        })
+    }
+
+    /// Parity index of an offset coordinate for [`Self::neighbor_offsets`]
+    #[expect(clippy::cast_sign_loss)]
+    const fn parity(ij: IVec2) -> usize {
+        ((ij.x & 1) | ((ij.y & 1) << 1)) as usize
+    }
+
     // ================================
     // Contains
     // ================================
@@ -466,6 +547,33 @@ impl RectMetadata {
     }
 }
 
+/// Coordinates are wrapped as by [`RectMetadata::cycle_hex`]: an odd
+/// dimension along the staggered offset axis is treated as
+/// [`WrapStrategy::Clamp`], as such a seam would make neighbors asymmetric
+/// and distances inconsistent.
+impl HexTopology for RectMetadata {
+    #[inline]
+    fn canonical(&self, hex: Hex) -> Option<Hex> {
+        self.cycle_hex(hex)
+    }
+
+    fn closest_image(&self, from: Hex, to: Hex) -> Hex {
+        let cycles = self.seamless_cycles();
+        let to = self.hex_to_offset(to);
+        let to = self.cycle_offset_along(to, cycles).unwrap_or(to);
+        let dim = self.dim.as_ivec2();
+        let [cx, cy] = cycles.map(|cycle| if cycle { -1..=1 } else { 0..=0 });
+        // Images are computed in offset coordinates to keep the wrapped lines
+        // aligned with the map
+        cx.flat_map(|i| {
+            cy.clone()
+                .map(move |j| self.ij_to_hex(to + IVec2::new(i * dim.x, j * dim.y)))
+        })
+        .min_by_key(|h| from.unsigned_distance_to(*h))
+        .unwrap_or_else(|| self.ij_to_hex(to))
+    }
+}
+
 impl<T> RectMap<T> {
     /// Creates and fills a rectangular shaped map
     ///
@@ -585,6 +693,49 @@ impl<T> RectMap<T> {
         }
     }
 
+    /// Returns mutable references to the values of the 6 neighbors of `hex`,
+    /// in [`EdgeDirection`](crate::EdgeDirection) order, wrapping along the
+    /// [`WrapStrategy::Cycle`] axes.
+    /// Out of bounds neighbors are `None`
+    ///
+    /// On maps too small along a cycling axis, a value may neighbor `hex` in
+    /// multiple directions: it is only returned for the first one.
+    ///
+    /// > See [`HexStore::neighbors`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let mut map = RectMetadata::from_half_size([4, 4])
+    ///     .with_wrap_strategies([WrapStrategy::Cycle, WrapStrategy::Clamp])
+    ///     .build_default::<u32>();
+    /// // On the west edge, neighbors wrap to the east edge
+    /// for value in map.neighbors_mut(hex(-4, 0)).into_iter().flatten() {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(map.values().sum::<u32>(), 6);
+    /// assert_eq!(map[hex(3, 0)], 1);
+    /// ```
+    #[must_use]
+    pub fn neighbors_mut(&mut self, hex: Hex) -> [Option<&mut T>; 6] {
+        let indices = hex
+            .all_neighbors()
+            .map(|neighbor| self.meta.cycle_hex_to_idx(neighbor));
+        disjoint_mut(&mut self.inner, indices)
+    }
+
+    /// Returns the coordinate at `idx` and the values of its neighbors, from
+    /// the precomputed [`RectMetadata::neighbor_offsets`]
+    fn neighbors_at(&self, offsets: &[[IVec2; 6]; 4], idx: usize) -> (Hex, [Option<&T>; 6]) {
+        let ij = self.meta.idx_to_ij(idx);
+        let neighbors = offsets[RectMetadata::parity(ij)].map(|offset| {
+            let neighbor = self.meta.cycle_offset(ij + offset)?;
+            Some(&self.inner[self.meta.ij_to_idx(neighbor)])
+        });
+        (self.meta.ij_to_hex(ij), neighbors)
+    }
+
     // ================================
     // Wrapped Index
     // ================================
@@ -630,6 +781,17 @@ impl<T> std::ops::Deref for RectMap<T> {
     }
 }
 
+impl<T> HexStoreMap<T> for RectMap<T> {
+    type Mapped<U> = RectMap<U>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        RectMap {
+            inner: self.iter().map(|(hex, value)| f(hex, value)).collect(),
+            meta: self.meta.clone(),
+        }
+    }
+}
+
 impl<T> HexStore<T> for RectMap<T> {
     fn get(&self, hex: crate::Hex) -> Option<&T> {
         let idx = self.meta.hex_to_idx(hex)?;
@@ -674,6 +836,62 @@ impl<T> HexStore<T> for RectMap<T> {
             (hex, value)
         })
     }
+
+    #[inline]
+    fn wrapped_coord(&self, hex: crate::Hex) -> Option<crate::Hex> {
+        self.meta.cycle_hex(hex)
+    }
+
+    fn neighbors(&self, hex: Hex) -> [Option<&T>; 6] {
+        hex.all_neighbors().map(|neighbor| {
+            let idx = self.meta.cycle_hex_to_idx(neighbor)?;
+            Some(&self.inner[idx])
+        })
+    }
+
+    fn iter_with_neighbors<'s>(
+        &'s self,
+    ) -> impl ExactSizeIterator<Item = (Hex, &'s T, [Option<&'s T>; 6])>
+    where
+        T: 's,
+    {
+        let offsets = self.meta.neighbor_offsets();
+        self.values().enumerate().map(move |(i, value)| {
+            let (hex, neighbors) = self.neighbors_at(&offsets, i);
+            (hex, value, neighbors)
+        })
+    }
+}
+
+impl<T> HexStoreStep<T> for RectMap<T> {
+    fn step_into(&self, buffer: &mut Self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T) {
+        buffer.meta.clone_from(&self.meta);
+        buffer.inner.clear();
+        buffer.inner.extend(
+            self.iter_with_neighbors()
+                .map(|(hex, value, neighbors)| rule(hex, value, neighbors)),
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    fn step_into_parallel(
+        &self,
+        buffer: &mut Self,
+        rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T + Sync,
+    ) where
+        T: Send + Sync,
+    {
+        let offsets = self.meta.neighbor_offsets();
+        buffer.meta.clone_from(&self.meta);
+        self.inner
+            .par_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let (hex, neighbors) = self.neighbors_at(&offsets, idx);
+                rule(hex, value, neighbors)
+            })
+            .collect_into_vec(&mut buffer.inner);
+    }
 }
 
 impl<T: Debug> Debug for RectMap<T> {
@@ -809,6 +1027,48 @@ mod half_size_test {
         }
     }
 
+    /// Tests neighbor accessors against wrapped coordinates
+    #[test]
+    fn neighbors_test() {
+        for dim in &HALF_SIZES[..6] {
+            for wrap_strategies in WRAP_STRATEGIES {
+                for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+                    for offset_mode in [OffsetHexMode::Odd, OffsetHexMode::Even] {
+                        let mut rect_map = RectMetadata::from_half_size(UVec2::from_array(*dim))
+                            .with_orientation(orientation)
+                            .with_offset_mode(offset_mode)
+                            .with_wrap_strategies(*wrap_strategies)
+                            .build(|h| h);
+                        let meta = rect_map.meta().clone();
+                        // Values are their coordinates
+                        let expected_neighbors =
+                            |h: Hex| h.all_neighbors().map(|n| meta.cycle_hex(n));
+                        for (h, v, neighbors) in rect_map.iter_with_neighbors() {
+                            assert_eq!(h, *v);
+                            assert_eq!(
+                                neighbors.map(Option::<&Hex>::copied),
+                                expected_neighbors(h)
+                            );
+                        }
+                        for h in meta.iter_hex() {
+                            let expected = expected_neighbors(h);
+                            assert_eq!(rect_map.neighbors(h).map(Option::<&Hex>::copied), expected);
+                            let neighbors_mut =
+                                rect_map.neighbors_mut(h).map(Option::<&mut Hex>::copied);
+                            for (i, n) in neighbors_mut.iter().enumerate() {
+                                // Repeated neighbors are only returned once
+                                let first = expected[..i]
+                                    .iter()
+                                    .all(|e| e.is_none() || *e != expected[i]);
+                                assert_eq!(*n, expected[i].filter(|_| first));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Tests value construction
     #[test]
     fn construction_test() {
@@ -1070,4 +1330,36 @@ mod start_dim_test {
             }
         }
     }
+
+    /// Tests that wrapped neighbors are symmetric, including odd dimensions
+    #[test]
+    fn neighbors_symmetry_test() {
+        for dim in [[7, 5], [5, 7], [3, 3], [6, 4]] {
+            for wrap_strategies in WRAP_STRATEGIES {
+                for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+                    for offset_mode in [OffsetHexMode::Odd, OffsetHexMode::Even] {
+                        let rect_map = RectMetadata::from_start_dim([-3, -2].into(), dim.into())
+                            .with_orientation(orientation)
+                            .with_offset_mode(offset_mode)
+                            .with_wrap_strategies(*wrap_strategies)
+                            .build(|h| h);
+                        // Values are their coordinates
+                        let neighbors = |h: Hex| rect_map.neighbors(h).map(Option::<&Hex>::copied);
+                        for (a, _, a_neighbors) in rect_map.iter_with_neighbors() {
+                            assert_eq!(a_neighbors.map(Option::<&Hex>::copied), neighbors(a));
+                            for b in rect_map.meta().iter_hex() {
+                                assert_eq!(
+                                    neighbors(a).contains(&Some(b)),
+                                    neighbors(b).contains(&Some(a))
+                                );
+                            }
+                            for (coord, n) in a.all_neighbors().into_iter().zip(neighbors(a)) {
+                                assert_eq!(rect_map.wrapped_coord(coord), n);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use super::HexStore;
+use super::{HexStore, HexStoreMap, HexStoreStep};
 use crate::Hex;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -181,6 +181,47 @@ impl<T> RombusMap<T> {
     }
 }
 
+impl<T> HexStoreStep<T> for RombusMap<T> {
+    fn step_into(&self, buffer: &mut Self, rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T) {
+        buffer.meta = self.meta;
+        buffer.inner.clear();
+        buffer.inner.extend(
+            self.iter_with_neighbors()
+                .map(|(hex, value, neighbors)| rule(hex, value, neighbors)),
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    fn step_into_parallel(
+        &self,
+        buffer: &mut Self,
+        rule: impl Fn(Hex, &T, [Option<&T>; 6]) -> T + Sync,
+    ) where
+        T: Send + Sync,
+    {
+        buffer.meta = self.meta;
+        self.inner
+            .par_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let hex = self.meta.idx_to_hex(idx);
+                rule(hex, value, self.neighbors(hex))
+            })
+            .collect_into_vec(&mut buffer.inner);
+    }
+}
+
+impl<T> HexStoreMap<T> for RombusMap<T> {
+    type Mapped<U> = RombusMap<U>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        RombusMap {
+            inner: self.iter().map(|(hex, value)| f(hex, value)).collect(),
+            meta: self.meta,
+        }
+    }
+}
+
 impl<T> HexStore<T> for RombusMap<T> {
     fn get(&self, hex: crate::Hex) -> Option<&T> {
         let index = self.meta.hex_to_idx(hex)?;
@@ -0,0 +1,354 @@
+use crate::{Hex, HexBounds, hex::ExactSizeHexIterator};
+use std::fmt;
+
+use super::{
+    HexStore, HexStoreMap, HexStoreMut, HexagonalMap,
+    bitset::{WORD_BITS, get_bit, iter_ones, set_bit},
+};
+
+/// Sparse storage for hexagonal maps, wrapping a
+/// [`HexagonalMap<Option<T>>`](HexagonalMap) with an occupancy bitset.
+///
+/// Unlike the dense storages, coordinates may be vacant: values can be
+/// inserted and removed within the map bounds (See [`HexStoreMut`]), while
+/// keeping the fast dense accessors. [`HexStore`] iterators only visit the
+/// occupied coordinates.
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::{*, storage::*};
+/// let mut map = SparseHexagonalMap::new(Hex::ZERO, 10);
+/// assert!(map.is_empty());
+/// assert_eq!(map.insert(hex(1, 2), "unit"), Ok(None));
+/// assert!(map.contains(hex(1, 2)));
+/// assert_eq!(map.len(), 1);
+/// // Out of bounds coordinates can't be stored
+/// assert_eq!(map.insert(hex(100, 2), "unit"), Err("unit"));
+/// assert_eq!(map.remove(hex(1, 2)), Some("unit"));
+/// assert!(map.is_empty());
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseHexagonalMap<T> {
+    inner: HexagonalMap<Option<T>>,
+    occupancy: Vec<u64>,
+    row_offsets: Vec<usize>,
+    len: usize,
+}
+
+impl<T> SparseHexagonalMap<T> {
+    /// Creates an empty hexagon shaped map
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The center coordinate of the hexagon
+    /// * `radius` - The radius of the map, around `center`
+    #[must_use]
+    pub fn new(center: Hex, radius: u32) -> Self {
+        Self::from(HexagonalMap::new(center, radius, |_| None))
+    }
+
+    /// Creates and fills a hexagon shaped map, vacant where `values` returns
+    /// `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The center coordinate of the hexagon
+    /// * `radius` - The radius of the map, around `center`
+    /// * `values` - Function called for each coordinate in the `radius` to fill
+    ///   the map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hexx::{*, storage::*};
+    /// let map = SparseHexagonalMap::from_fn(Hex::ZERO, 10, |c| (c.x > 0).then_some(c.y));
+    /// assert_eq!(map.get(hex(1, 2)), Some(&2));
+    /// assert_eq!(map.get(hex(-1, 2)), None);
+    /// ```
+    #[must_use]
+    pub fn from_fn(center: Hex, radius: u32, values: impl FnMut(Hex) -> Option<T>) -> Self {
+        Self::from(HexagonalMap::new(center, radius, values))
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the associated coordinate bounds
+    pub const fn bounds(&self) -> &HexBounds {
+        self.inner.bounds()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Amount of stored values
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns `true` if no value is stored
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the wrapped dense storage
+    pub const fn inner(&self) -> &HexagonalMap<Option<T>> {
+        &self.inner
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the wrapped dense storage
+    pub fn into_inner(self) -> HexagonalMap<Option<T>> {
+        self.inner
+    }
+
+    /// Returns the flat index of `hex`, matching the iteration order of the
+    /// dense storage
+    fn flat_index(&self, hex: Hex) -> Option<usize> {
+        let [y, x] = self.inner.index_of(hex)?;
+        Some(self.row_offsets[y] + x)
+    }
+
+    /// Returns the coordinate at the flat `index`
+    fn coord_at(&self, index: usize) -> Hex {
+        let y = self.row_offsets.partition_point(|o| *o <= index) - 1;
+        self.inner.coord_at([y, index - self.row_offsets[y]])
+    }
+
+    /// Returns `true` if a value is stored at `hex`, without accessing the
+    /// value
+    #[must_use]
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.flat_index(hex)
+            .is_some_and(|i| get_bit(&self.occupancy, i))
+    }
+
+    /// Iterator over the occupied coordinates, scanning only the occupancy
+    /// bitset
+    #[must_use]
+    pub fn occupied(&self) -> impl ExactSizeIterator<Item = Hex> {
+        let iter = iter_ones(&self.occupancy).map(|index| self.coord_at(index));
+        ExactSizeHexIterator {
+            iter,
+            count: self.len,
+        }
+    }
+}
+
+impl<T> From<HexagonalMap<Option<T>>> for SparseHexagonalMap<T> {
+    fn from(inner: HexagonalMap<Option<T>>) -> Self {
+        let mut row_offsets = vec![0];
+        row_offsets.extend(inner.row_lens().scan(0, |offset, len| {
+            *offset += len;
+            Some(*offset)
+        }));
+        let mut occupancy = vec![0; inner.len().div_ceil(WORD_BITS)];
+        let mut len = 0;
+        for (i, value) in inner.values().enumerate() {
+            if value.is_some() {
+                set_bit(&mut occupancy, i, true);
+                len += 1;
+            }
+        }
+        Self {
+            inner,
+            occupancy,
+            row_offsets,
+            len,
+        }
+    }
+}
+
+impl<T> HexStore<T> for SparseHexagonalMap<T> {
+    fn get(&self, hex: Hex) -> Option<&T> {
+        self.inner.get(hex)?.as_ref()
+    }
+
+    fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.inner.get_mut(hex)?.as_mut()
+    }
+
+    fn values<'s>(&'s self) -> impl ExactSizeIterator<Item = &'s T>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self.inner.values().flatten(),
+        }
+    }
+
+    fn values_mut<'s>(&'s mut self) -> impl ExactSizeIterator<Item = &'s mut T>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self.inner.values_mut().flatten(),
+        }
+    }
+
+    fn iter<'s>(&'s self) -> impl ExactSizeIterator<Item = (Hex, &'s T)>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self
+                .inner
+                .iter()
+                .filter_map(|(h, v)| Some((h, v.as_ref()?))),
+        }
+    }
+
+    fn iter_mut<'s>(&'s mut self) -> impl ExactSizeIterator<Item = (Hex, &'s mut T)>
+    where
+        T: 's,
+    {
+        ExactSizeHexIterator {
+            count: self.len,
+            iter: self
+                .inner
+                .iter_mut()
+                .filter_map(|(h, v)| Some((h, v.as_mut()?))),
+        }
+    }
+}
+
+impl<T> HexStoreMut<T> for SparseHexagonalMap<T> {
+    fn can_insert(&self, hex: Hex) -> bool {
+        self.bounds().is_in_bounds(hex)
+    }
+
+    fn insert(&mut self, hex: Hex, value: T) -> Result<Option<T>, T> {
+        let Some(index) = self.flat_index(hex) else {
+            return Err(value);
+        };
+        let Some(slot) = self.inner.get_mut(hex) else {
+            return Err(value);
+        };
+        let previous = slot.replace(value);
+        if previous.is_none() {
+            set_bit(&mut self.occupancy, index, true);
+            self.len += 1;
+        }
+        Ok(previous)
+    }
+
+    fn remove(&mut self, hex: Hex) -> Option<T> {
+        let index = self.flat_index(hex)?;
+        let value = self.inner.get_mut(hex)?.take()?;
+        set_bit(&mut self.occupancy, index, false);
+        self.len -= 1;
+        Some(value)
+    }
+}
+
+impl<T> HexStoreMap<T> for SparseHexagonalMap<T> {
+    type Mapped<U> = SparseHexagonalMap<U>;
+
+    fn map_values<U>(&self, mut f: impl FnMut(Hex, &T) -> U) -> Self::Mapped<U> {
+        SparseHexagonalMap {
+            inner: self.inner.map_values(|h, v| v.as_ref().map(|v| f(h, v))),
+            occupancy: self.occupancy.clone(),
+            row_offsets: self.row_offsets.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SparseHexagonalMap<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SparseHexagonalMap")
+            .field("inner", &self.inner)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ChunkedMap;
+    #[cfg(feature = "bevy_platform")]
+    use bevy_platform::collections::HashMap;
+    #[cfg(not(feature = "bevy_platform"))]
+    use std::collections::HashMap;
+
+    #[test]
+    fn occupancy() {
+        for radius in 0_u32..12 {
+            let center = Hex::new(3, -7);
+            let map = SparseHexagonalMap::from_fn(center, radius, |h| {
+                (h.x.rem_euclid(3) == 0).then_some(h)
+            });
+            let expected: Vec<Hex> = center
+                .range(radius)
+                .filter(|h| h.x.rem_euclid(3) == 0)
+                .collect();
+            assert_eq!(map.len(), expected.len());
+            let mut occupied: Vec<Hex> = map.occupied().collect();
+            let mut iter: Vec<Hex> = map
+                .iter()
+                .map(|(h, v)| {
+                    assert_eq!(h, *v);
+                    h
+                })
+                .collect();
+            occupied.sort_unstable_by_key(|h| h.to_array());
+            iter.sort_unstable_by_key(|h| h.to_array());
+            let mut expected = expected;
+            expected.sort_unstable_by_key(|h| h.to_array());
+            assert_eq!(occupied, expected);
+            assert_eq!(iter, expected);
+            for coord in center.range(radius + 1) {
+                assert_eq!(map.contains(coord), expected.contains(&coord));
+            }
+        }
+    }
+
+    /// Generic algorithm over mutable storages
+    fn fill_and_prune<S: HexStoreMut<usize>>(store: &mut S) -> usize {
+        for (i, coord) in Hex::ZERO.range(6).enumerate() {
+            if let Some(entry) = store.entry(coord) {
+                entry.or_insert(i);
+            }
+        }
+        for coord in Hex::ZERO.ring(6) {
+            store.remove(coord);
+        }
+        for coord in Hex::ZERO.range(2) {
+            if let Some(entry) = store.entry(coord) {
+                let _ = entry.and_modify(|v| *v += 1000).or_insert(0);
+            }
+        }
+        store.values().filter(|v| **v >= 1000).count()
+    }
+
+    #[test]
+    fn generic_stores() {
+        let mut hashmap = HashMap::new();
+        let mut chunked = ChunkedMap::new(2);
+        let mut sparse = SparseHexagonalMap::new(Hex::ZERO, 5);
+        let modified = Hex::range_count(2) as usize;
+        assert_eq!(fill_and_prune(&mut hashmap), modified);
+        assert_eq!(fill_and_prune(&mut chunked), modified);
+        assert_eq!(fill_and_prune(&mut sparse), modified);
+        let len = Hex::range_count(5) as usize;
+        assert_eq!(hashmap.len(), len);
+        assert_eq!(chunked.len(), len);
+        assert_eq!(sparse.len(), len);
+        for (coord, value) in &hashmap {
+            assert_eq!(chunked.get(*coord), Some(value));
+            assert_eq!(sparse.get(*coord), Some(value));
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{EdgeDirection, Hex, HexBounds};
+
+/// Describes the shape of a hexagonal world and how coordinates wrap around
+/// it, allowing neighbors, distances and lines to cross the seams of
+/// cylindrical or toroidal maps.
+///
+/// Implemented for:
+/// - [`Unbounded`], the default infinite grid without wrapping
+/// - [`HexBounds`], wrapping hexagonal maps (See [`HexBounds::wrap`])
+/// - [`RectMetadata`](crate::storage::RectMetadata), rectangular maps wrapping
+///   along their [`WrapStrategy::Cycle`](crate::storage::WrapStrategy::Cycle)
+///   axes
+///
+/// # Example
+///
+/// ```rust
+/// # use hexx::*;
+/// let bounds = HexBounds::new(Hex::ZERO, 5);
+/// let [a, b] = [hex(5, 0), hex(-5, 5)];
+/// // Both coordinates are on opposite sides of the map ..
+/// assert_eq!(a.unsigned_distance_to(b), 10);
+/// // .. but close through the seam
+/// assert_eq!(bounds.distance(a, b), 1);
+/// assert!(bounds.neighbors(a).contains(&Some(b)));
+/// ```
+pub trait HexTopology {
+    /// Returns the canonical coordinate of `hex`, wrapping it if needed.
+    ///
+    /// Returns `None` if `hex` is outside of the world and can't be wrapped
+    #[must_use]
+    fn canonical(&self, hex: Hex) -> Option<Hex>;
+
+    /// Returns the representative of `to` which is the closest to `from`,
+    /// which may be outside of the world.
+    ///
+    /// For non wrapping topologies this is `to`
+    #[must_use]
+    fn closest_image(&self, from: Hex, to: Hex) -> Hex;
+
+    /// Returns the canonical neighbor of `hex` in the given `direction`, or
+    /// `None` if it is outside of the world
+    #[inline]
+    #[must_use]
+    fn neighbor(&self, hex: Hex, direction: EdgeDirection) -> Option<Hex> {
+        self.canonical(hex + direction)
+    }
+
+    /// Returns the canonical neighbors of `hex` in [`EdgeDirection`] order
+    #[must_use]
+    fn neighbors(&self, hex: Hex) -> [Option<Hex>; 6] {
+        EdgeDirection::ALL_DIRECTIONS.map(|dir| self.neighbor(hex, dir))
+    }
+
+    /// Computes the shortest distance between `a` and `b`, taking wrapping in
+    /// account
+    #[must_use]
+    fn distance(&self, a: Hex, b: Hex) -> u32 {
+        a.unsigned_distance_to(self.closest_image(a, b))
+    }
+
+    /// Computes the shortest line from `a` to `b`, taking wrapping in account.
+    ///
+    /// The returned coordinates are canonical, coordinates outside of the
+    /// world are skipped
+    ///
+    /// > See [`Hex::line_to`]
+    fn line_to(&self, a: Hex, b: Hex) -> impl Iterator<Item = Hex> {
+        a.line_to(self.closest_image(a, b))
+            .filter_map(|h| self.canonical(h))
+    }
+}
+
+/// Infinite hexagonal grid, without any wrapping.
+///
+/// This is the default [`HexTopology`] used by the algorithms
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Unbounded;
+
+impl HexTopology for Unbounded {
+    #[inline]
+    fn canonical(&self, hex: Hex) -> Option<Hex> {
+        Some(hex)
+    }
+
+    #[inline]
+    fn closest_image(&self, _from: Hex, to: Hex) -> Hex {
+        to
+    }
+
+    #[inline]
+    fn distance(&self, a: Hex, b: Hex) -> u32 {
+        a.unsigned_distance_to(b)
+    }
+}
+
+impl HexTopology for HexBounds {
+    #[inline]
+    fn canonical(&self, hex: Hex) -> Option<Hex> {
+        Some(self.wrap(hex))
+    }
+
+    fn closest_image(&self, from: Hex, to: Hex) -> Hex {
+        // The wrapped offset is the smallest offset between both images
+        from + (to - from).wrap_in_range(self.radius)
+    }
+}
+
+impl<T: HexTopology + ?Sized> HexTopology for &T {
+    #[inline]
+    fn canonical(&self, hex: Hex) -> Option<Hex> {
+        (**self).canonical(hex)
+    }
+
+    #[inline]
+    fn closest_image(&self, from: Hex, to: Hex) -> Hex {
+        (**self).closest_image(from, to)
+    }
+
+    #[inline]
+    fn distance(&self, a: Hex, b: Hex) -> u32 {
+        (**self).distance(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        HexOrientation,
+        storage::{RectMetadata, WrapStrategy},
+    };
+    use glam::{IVec2, UVec2};
+    use std::collections::{HashMap, VecDeque, hash_map::Entry};
+
+    #[test]
+    fn bounds_distances() {
+        let bounds = HexBounds::new(Hex::new(3, -2), 4);
+        for a in bounds.all_coords() {
+            let neighbors = bounds.neighbors(a);
+            for b in bounds.all_coords() {
+                let distance = bounds.distance(a, b);
+                assert!(distance <= a.unsigned_distance_to(b));
+                assert!(distance <= bounds.radius);
+                assert_eq!(distance, bounds.distance(b, a));
+                assert_eq!(distance == 1, neighbors.contains(&Some(b)));
+                let line: Vec<_> = bounds.line_to(a, b).collect();
+                assert_eq!(line.len(), distance as usize + 1);
+                assert_eq!(line.last(), Some(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn rect_distances() {
+        let meta = RectMetadata::from_half_size([6, 4])
+            .with_orientation(HexOrientation::Pointy)
+            .with_wrap_strategies([WrapStrategy::Cycle, WrapStrategy::Clamp]);
+        for a in meta.iter_hex() {
+            let neighbors = meta.neighbors(a);
+            assert!(neighbors.iter().flatten().all(|n| meta.contains_hex(*n)));
+            for b in meta.iter_hex() {
+                let distance = meta.distance(a, b);
+                assert!(distance <= a.unsigned_distance_to(b));
+                assert_eq!(distance, meta.distance(b, a));
+                assert_eq!(distance == 1, neighbors.contains(&Some(b)));
+            }
+        }
+        // Coordinates on both sides of the seam
+        let [a, b] = [Hex::new(-6, 0), Hex::new(5, 0)];
+        assert_eq!(meta.distance(a, b), 1);
+        assert_eq!(meta.neighbor(b, EdgeDirection::POINTY_RIGHT), Some(a));
+        // Clamped rows are not wrapped
+        assert_eq!(meta.canonical(Hex::new(0, -5)), None);
+    }
+
+    #[test]
+    fn rect_distances_match_neighbors() {
+        let strategies = [
+            [WrapStrategy::Cycle, WrapStrategy::Clamp],
+            [WrapStrategy::Clamp, WrapStrategy::Cycle],
+            [WrapStrategy::Cycle; 2],
+        ];
+        for dim in [UVec2::new(7, 5), UVec2::new(8, 6)] {
+            for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+                for wrap in strategies {
+                    let meta = RectMetadata::from_start_dim(IVec2::new(-3, -2), dim)
+                        .with_orientation(orientation)
+                        .with_wrap_strategies(wrap);
+                    for a in meta.iter_hex() {
+                        // Breadth first search over the topology neighbors
+                        let mut distances = HashMap::from([(a, 0)]);
+                        let mut queue = VecDeque::from([a]);
+                        while let Some(hex) = queue.pop_front() {
+                            let next = distances[&hex] + 1;
+                            for n in meta.neighbors(hex).into_iter().flatten() {
+                                assert!(meta.neighbors(n).contains(&Some(hex)));
+                                if let Entry::Vacant(entry) = distances.entry(n) {
+                                    entry.insert(next);
+                                    queue.push_back(n);
+                                }
+                            }
+                        }
+                        for b in meta.iter_hex() {
+                            assert_eq!(meta.distance(a, b), distances[&b]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}